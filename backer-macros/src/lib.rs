@@ -0,0 +1,539 @@
+/*!
+The `layout!` macro for [backer](https://docs.rs/backer), enabled by backer's `macros` feature.
+*/
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    braced, parenthesized,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    token, Expr, ExprLit, ExprUnary, Ident, Lit, LitFloat, Pat, Token, UnOp,
+};
+
+/// Builds a node tree from nested rows, columns & stacks without `vec![...]` staircases.
+///
+/// See `backer::layout!` for the syntax.
+#[proc_macro]
+pub fn layout(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    match syn::parse::<Root>(input) {
+        Ok(root) => root.0.expand().into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+const CONTAINERS: &[&str] = &["column", "row", "stack", "flow", "group", "grid", "scroll"];
+
+/// Modifiers which take `f32`s, so that integer literals can be used as arguments
+const FLOAT_MODIFIERS: &[&str] = &[
+    "pad",
+    "pad_x",
+    "pad_y",
+    "pad_top",
+    "pad_bottom",
+    "pad_leading",
+    "pad_trailing",
+    "offset",
+    "offset_x",
+    "offset_y",
+    "width",
+    "height",
+    "aspect",
+    "grow",
+    "shrink",
+];
+
+struct Root(Element);
+
+impl Parse for Root {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.is_empty() {
+            return Err(input.error("expected a node"));
+        }
+        if input.peek(Token![if]) || input.peek(Token![for]) {
+            return Err(input.error("`if` & `for` can only be used in a list of children"));
+        }
+        let root = input.parse()?;
+        if !input.is_empty() {
+            return Err(
+                input.error("expected a single root node, wrap multiple nodes in a container")
+            );
+        }
+        Ok(Self(root))
+    }
+}
+
+enum Child {
+    Element(Element),
+    If(IfChildren),
+    For(ForChildren),
+}
+
+struct IfChildren {
+    if_token: Token![if],
+    condition: Expr,
+    then: Vec<Child>,
+    otherwise: Option<(Token![else], Else)>,
+}
+
+enum Else {
+    If(Box<IfChildren>),
+    Children(Vec<Child>),
+}
+
+struct ForChildren {
+    for_token: Token![for],
+    pattern: Pat,
+    in_token: Token![in],
+    iterator: Expr,
+    body: Vec<Child>,
+}
+
+enum Element {
+    Container(Container),
+    Expr(Expr),
+}
+
+struct Container {
+    name: Ident,
+    arguments: Vec<Argument>,
+    children: Vec<Child>,
+    modifiers: Vec<Modifier>,
+}
+
+struct Argument {
+    name: Ident,
+    value: Expr,
+}
+
+struct Modifier {
+    dot: Token![.],
+    name: Ident,
+    arguments: Punctuated<Expr, Token![,]>,
+}
+
+/// Parses children until the end of `input`, with optional commas between them
+fn parse_children(input: ParseStream) -> syn::Result<Vec<Child>> {
+    let mut children = Vec::new();
+    while !input.is_empty() {
+        children.push(input.parse()?);
+        input.parse::<Option<Token![,]>>()?;
+    }
+    Ok(children)
+}
+
+fn parse_braced_children(input: ParseStream) -> syn::Result<Vec<Child>> {
+    let content;
+    braced!(content in input);
+    parse_children(&content)
+}
+
+impl Parse for Child {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![if]) {
+            input.parse().map(Child::If)
+        } else if input.peek(Token![for]) {
+            Ok(Child::For(ForChildren {
+                for_token: input.parse()?,
+                pattern: Pat::parse_multi_with_leading_vert(input)?,
+                in_token: input.parse()?,
+                iterator: Expr::parse_without_eager_brace(input)?,
+                body: parse_braced_children(input)?,
+            }))
+        } else {
+            input.parse().map(Child::Element)
+        }
+    }
+}
+
+impl Parse for IfChildren {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            if_token: input.parse()?,
+            condition: Expr::parse_without_eager_brace(input)?,
+            then: parse_braced_children(input)?,
+            otherwise: if input.peek(Token![else]) {
+                let else_token = input.parse()?;
+                let otherwise = if input.peek(Token![if]) {
+                    Else::If(Box::new(input.parse()?))
+                } else {
+                    Else::Children(parse_braced_children(input)?)
+                };
+                Some((else_token, otherwise))
+            } else {
+                None
+            },
+        })
+    }
+}
+
+/// Whether `input` starts with `name { ... }` or `name(...) { ... }`
+fn starts_container(input: ParseStream) -> bool {
+    let fork = input.fork();
+    if fork.parse::<Ident>().is_err() {
+        return false;
+    }
+    if fork.peek(token::Paren) && fork.parse::<proc_macro2::TokenTree>().is_err() {
+        return false;
+    }
+    fork.peek(token::Brace)
+}
+
+impl Parse for Element {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if !starts_container(input) {
+            let mut expr: Expr = input.parse()?;
+            float_modifier_arguments(&mut expr);
+            return Ok(Element::Expr(expr));
+        }
+        let name: Ident = input.parse()?;
+        if !CONTAINERS.contains(&name.to_string().as_str()) {
+            return Err(syn::Error::new(
+                name.span(),
+                format!(
+                    "unknown container `{name}`, expected one of {}",
+                    CONTAINERS
+                        .iter()
+                        .map(|container| format!("`{container}`"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            ));
+        }
+        let arguments = if input.peek(token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            Punctuated::<Argument, Token![,]>::parse_terminated(&content)?
+                .into_iter()
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let children = parse_braced_children(input)?;
+        let mut modifiers = Vec::new();
+        while input.peek(Token![.]) {
+            let dot = input.parse()?;
+            let name: Ident = input.parse()?;
+            let content;
+            parenthesized!(content in input);
+            let mut arguments = Punctuated::parse_terminated(&content)?;
+            if FLOAT_MODIFIERS.contains(&name.to_string().as_str()) {
+                arguments.iter_mut().for_each(float_literal);
+            }
+            modifiers.push(Modifier {
+                dot,
+                name,
+                arguments,
+            });
+        }
+        Ok(Element::Container(Container {
+            name,
+            arguments,
+            children,
+            modifiers,
+        }))
+    }
+}
+
+impl Parse for Argument {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![:]>()?;
+        Ok(Self {
+            name,
+            value: input.parse()?,
+        })
+    }
+}
+
+/// Rewrites an integer literal such as `10` or `-10` as a float literal
+fn float_literal(expr: &mut Expr) {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(int), ..
+        }) if int.suffix().is_empty() => {
+            let float = LitFloat::new(&format!("{}.", int.base10_digits()), int.span());
+            *expr = Expr::Lit(ExprLit {
+                attrs: Vec::new(),
+                lit: Lit::Float(float),
+            });
+        }
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => float_literal(expr),
+        _ => {}
+    }
+}
+
+/// Rewrites integer literal arguments along a chain of modifiers such as `space().width(10).pad(5)`
+fn float_modifier_arguments(expr: &mut Expr) {
+    if let Expr::MethodCall(call) = expr {
+        if FLOAT_MODIFIERS.contains(&call.method.to_string().as_str()) {
+            call.args.iter_mut().for_each(float_literal);
+        }
+        float_modifier_arguments(&mut call.receiver);
+    }
+}
+
+impl Child {
+    /// Pushes the child's nodes onto `list`
+    fn push(&self, list: &Ident) -> TokenStream {
+        match self {
+            Child::Element(element) => {
+                let element = element.expand();
+                quote!(#list.push(#element);)
+            }
+            Child::If(children) => children.push(list),
+            Child::For(ForChildren {
+                for_token,
+                pattern,
+                in_token,
+                iterator,
+                body,
+            }) => {
+                let body = body.iter().map(|child| child.push(list));
+                quote!(#for_token #pattern #in_token #iterator { #(#body)* })
+            }
+        }
+    }
+}
+
+impl IfChildren {
+    fn push(&self, list: &Ident) -> TokenStream {
+        let IfChildren {
+            if_token,
+            condition,
+            then,
+            otherwise,
+        } = self;
+        let then = then.iter().map(|child| child.push(list));
+        let otherwise = otherwise
+            .as_ref()
+            .map(|(else_token, otherwise)| match otherwise {
+                Else::If(children) => {
+                    let children = children.push(list);
+                    quote!(#else_token #children)
+                }
+                Else::Children(children) => {
+                    let children = children.iter().map(|child| child.push(list));
+                    quote!(#else_token { #(#children)* })
+                }
+            });
+        quote!(#if_token #condition { #(#then)* } #otherwise)
+    }
+}
+
+/// Builds a `Vec` of nodes from `children`
+fn expand_children(children: &[Child]) -> TokenStream {
+    let elements: Option<Vec<_>> = children
+        .iter()
+        .map(|child| match child {
+            Child::Element(element) => Some(element.expand()),
+            _ => None,
+        })
+        .collect();
+    if let Some(elements) = elements {
+        return quote!(::std::vec![#(#elements),*]);
+    }
+    let list = Ident::new("children", Span::mixed_site());
+    let pushes = children.iter().map(|child| child.push(&list));
+    quote!({
+        let mut #list = ::std::vec::Vec::new();
+        #(#pushes)*
+        #list
+    })
+}
+
+impl Element {
+    fn expand(&self) -> TokenStream {
+        match self {
+            Element::Expr(expr) => quote!(#expr),
+            Element::Container(container) => container
+                .expand()
+                .unwrap_or_else(|error| error.to_compile_error()),
+        }
+    }
+}
+
+impl Container {
+    /// Takes the arguments named in `allowed`, in order, reporting unknown & repeated arguments
+    fn arguments<const N: usize>(&self, allowed: [&str; N]) -> syn::Result<[Option<&Expr>; N]> {
+        let mut found = [None; N];
+        for argument in &self.arguments {
+            let name = argument.name.to_string();
+            let Some(index) = allowed.iter().position(|allowed| *allowed == name) else {
+                let expected = if allowed.is_empty() {
+                    format!("`{}` doesn't take any arguments", self.name)
+                } else {
+                    format!(
+                        "expected {}",
+                        allowed
+                            .iter()
+                            .map(|allowed| format!("`{allowed}`"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                };
+                return Err(syn::Error::new(
+                    argument.name.span(),
+                    format!("unknown argument `{name}` for `{}`, {expected}", self.name),
+                ));
+            };
+            if found[index].replace(&argument.value).is_some() {
+                return Err(syn::Error::new(
+                    argument.name.span(),
+                    format!("duplicate argument `{name}`"),
+                ));
+            }
+        }
+        Ok(found)
+    }
+
+    fn required<'a>(&self, argument: Option<&'a Expr>, name: &str) -> syn::Result<&'a Expr> {
+        argument.ok_or_else(|| {
+            syn::Error::new(
+                self.name.span(),
+                format!("`{}` requires a `{name}` argument", self.name),
+            )
+        })
+    }
+
+    fn expand(&self) -> syn::Result<TokenStream> {
+        let function = |name: &str| Ident::new(name, self.name.span());
+        let float = |argument: Option<&Expr>| {
+            let mut argument = argument.cloned().unwrap_or_else(|| syn::parse_quote!(0.));
+            float_literal(&mut argument);
+            argument
+        };
+        let children = expand_children(&self.children);
+        let node = match self.name.to_string().as_str() {
+            name @ ("column" | "row") => match self.arguments(["spacing"])? {
+                [None] => {
+                    let function = function(name);
+                    quote!(::backer::nodes::#function(#children))
+                }
+                [spacing] => {
+                    let function = function(&format!("{name}_spaced"));
+                    let spacing = float(spacing);
+                    quote!(::backer::nodes::#function(#spacing, #children))
+                }
+            },
+            name @ ("stack" | "group") => {
+                self.arguments([])?;
+                let function = function(name);
+                quote!(::backer::nodes::#function(#children))
+            }
+            "flow" => match self.arguments(["spacing", "line_spacing"])? {
+                [None, None] => {
+                    let function = function("flow");
+                    quote!(::backer::nodes::#function(#children))
+                }
+                [spacing, line_spacing] => {
+                    let function = function("flow_spaced");
+                    let (spacing, line_spacing) = (float(spacing), float(line_spacing));
+                    quote!(::backer::nodes::#function(#spacing, #line_spacing, #children))
+                }
+            },
+            "grid" => {
+                let [columns, rows, column_gap, row_gap] =
+                    self.arguments(["columns", "rows", "column_gap", "row_gap"])?;
+                let columns = self.required(columns, "columns")?;
+                let rows = self.required(rows, "rows")?;
+                if column_gap.is_none() && row_gap.is_none() {
+                    let function = function("grid");
+                    quote!(::backer::nodes::#function(#columns, #rows, #children))
+                } else {
+                    let function = function("grid_spaced");
+                    let (column_gap, row_gap) = (float(column_gap), float(row_gap));
+                    quote!(::backer::nodes::#function(#column_gap, #row_gap, #columns, #rows, #children))
+                }
+            }
+            "scroll" => {
+                let [axis, offset] = self.arguments(["axis", "offset"])?;
+                let axis = self.required(axis, "axis")?;
+                let offset = float(offset);
+                let [Child::Element(child)] = self.children.as_slice() else {
+                    return Err(syn::Error::new(
+                        self.name.span(),
+                        "`scroll` must contain exactly one node",
+                    ));
+                };
+                let child = child.expand();
+                let function = function("scroll");
+                quote!(::backer::nodes::#function(#axis, #offset, #child))
+            }
+            _ => unreachable!("containers are checked while parsing"),
+        };
+        let modifiers = self.modifiers.iter().map(
+            |Modifier {
+                 dot,
+                 name,
+                 arguments,
+             }| quote!(#dot #name(#arguments)),
+        );
+        Ok(quote!(#node #(#modifiers)*))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(input: TokenStream) -> String {
+        match syn::parse2::<Root>(input) {
+            Ok(root) => root.0.expand().to_string(),
+            Err(error) => error.to_compile_error().to_string(),
+        }
+    }
+
+    #[test]
+    fn test_expand_containers() {
+        let root: Root = syn::parse2(quote!(column(spacing: 10) {
+            row { space(), space().width(5) }.pad(-5)
+        }))
+        .unwrap();
+        assert_eq!(
+            root.0.expand().to_string(),
+            quote!(::backer::nodes::column_spaced(
+                10.,
+                ::std::vec![::backer::nodes::row(::std::vec![space(), space().width(5.)]).pad(-5.)]
+            ))
+            .to_string()
+        );
+    }
+    #[test]
+    fn test_errors() {
+        for (input, message) in [
+            (quote!(), "expected a node"),
+            (quote!(space() space()), "expected a single root node"),
+            (quote!(colum {}), "unknown container `colum`"),
+            (
+                quote!(stack(spacing: 1) { }),
+                "unknown argument `spacing` for `stack`, `stack` doesn't take any arguments",
+            ),
+            (
+                quote!(row(spacing: 1, spacing: 2) { }),
+                "duplicate argument `spacing`",
+            ),
+            (
+                quote!(grid(columns: vec![]) { }),
+                "`grid` requires a `rows` argument",
+            ),
+            (
+                quote!(scroll(axis: Axis::Vertical) { }),
+                "`scroll` must contain exactly one node",
+            ),
+            (
+                quote!(if true {
+                    space()
+                }),
+                "`if` & `for` can only be used in a list of children",
+            ),
+        ] {
+            let error = error(input);
+            assert!(error.contains(message), "{error} doesn't contain {message}");
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{constraints::SizeConstraints, models::Area};
+use std::{collections::HashMap, fmt::Debug, mem};
+
+type CacheEntryKey = (u64, [u32; 2]);
+/// Constraints, along with whether they depend on the area they were measured in
+type CacheEntry = (Option<SizeConstraints>, bool);
+
+/// Constraints of subtrees marked with [`crate::Node::cache_key`], retained between layout passes.
+///
+/// A [`crate::Layout`] owns it's cache, so a layout which is kept between frames
+/// caches automatically. When a new layout is created every frame, move the cache between them
+/// using [`crate::Layout::with_cache`] & [`crate::Layout::take_cache`].
+///
+/// Keys are scoped to the position of the subtree in the tree,
+/// so the same key can be used by several subtrees.
+///
+/// Between layouts created every frame, only measurement is skipped:
+/// `dynamic` & `area_reader` closures in an unchanged subtree are still called once to lay it out,
+/// since the nodes built by the previous layout don't outlive it.
+/// A layout which is kept between frames also reuses those nodes, see [`crate::Node::cache_key`].
+///
+/// Entries which aren't used during a pass are dropped at the start of the following pass.
+#[derive(Debug, Default)]
+pub struct LayoutCache {
+    current: HashMap<CacheEntryKey, CacheEntry>,
+    previous: HashMap<CacheEntryKey, CacheEntry>,
+}
+
+impl LayoutCache {
+    pub(crate) fn begin_pass(&mut self) {
+        self.previous = mem::take(&mut self.current);
+    }
+    pub(crate) fn get(&mut self, key: u64, area: Area) -> Option<CacheEntry> {
+        let key = entry_key(key, area);
+        if let Some(constraints) = self.current.get(&key) {
+            return Some(*constraints);
+        }
+        let constraints = self.previous.remove(&key)?;
+        self.current.insert(key, constraints);
+        Some(constraints)
+    }
+    pub(crate) fn insert(&mut self, key: u64, area: Area, entry: CacheEntry) {
+        self.current.insert(entry_key(key, area), entry);
+    }
+}
+
+/// The key of a cached subtree, see [`crate::Node::cache_key`]
+pub(crate) enum CacheKey<'nodes, State> {
+    Fixed(u64),
+    /// Read from the state each time the subtree is measured or laid out
+    Computed(Box<dyn Fn(&State) -> u64 + 'nodes>),
+}
+
+impl<State> CacheKey<'_, State> {
+    pub(crate) fn get(&self, state: &State) -> u64 {
+        match self {
+            CacheKey::Fixed(key) => *key,
+            CacheKey::Computed(key) => key(state),
+        }
+    }
+}
+
+impl<State> Debug for CacheKey<'_, State> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CacheKey::Fixed(key) => f.debug_tuple("Fixed").field(key).finish(),
+            CacheKey::Computed(_) => f.debug_tuple("Computed").finish(),
+        }
+    }
+}
+
+/// Constraints depend on the size of the available area, so subtrees which move keep their entries
+fn entry_key(key: u64, area: Area) -> CacheEntryKey {
+    (key, [area.width.to_bits(), area.height.to_bits()])
+}
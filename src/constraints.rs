@@ -1,6 +1,11 @@
 use crate::{
-    layout::NodeValue,
-    models::{Area, Size, XAlign, YAlign},
+    context::LayoutCtx,
+    grid::grid_constraints,
+    layout::{flow_lines, NodeValue},
+    models::{
+        Area, Axis, Bounds, Constraints, DiagnosticKind, MeasuredSize, ProposedSize, Size, XAlign,
+        YAlign,
+    },
     node_cache::NodeCache,
 };
 
@@ -13,6 +18,8 @@ pub(crate) struct SizeConstraints {
     pub(crate) expand_y: bool,
     pub(crate) x_align: Option<XAlign>,
     pub(crate) y_align: Option<YAlign>,
+    pub(crate) grow: Option<f32>,
+    pub(crate) shrink: Option<f32>,
 }
 
 impl Default for SizeConstraints {
@@ -25,10 +32,17 @@ impl Default for SizeConstraints {
             expand_y: false,
             x_align: None,
             y_align: None,
+            grow: None,
+            shrink: None,
         }
     }
 }
 
+/// The range of sizes a node can take along one axis.
+///
+/// Invalid bounds are resolved rather than rejected: NaN bounds are ignored,
+/// & a lower bound larger than the upper bound raises the upper bound to match,
+/// the same policy as `DiagnosticKind::MinGreaterThanMax`.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub(crate) struct Constraint {
     lower: Option<f32>,
@@ -37,28 +51,24 @@ pub(crate) struct Constraint {
 
 impl Constraint {
     pub(crate) fn new(lower: Option<f32>, upper: Option<f32>) -> Self {
-        assert!(Self::check_constraints(lower, upper));
+        let lower = lower.filter(|lower| !lower.is_nan());
+        let upper = match (lower, upper.filter(|upper| !upper.is_nan())) {
+            (Some(lower), Some(upper)) => Some(upper.max(lower)),
+            (_, upper) => upper,
+        };
         Self { lower, upper }
     }
     pub(crate) fn get_lower(&self) -> Option<f32> {
         self.lower
     }
     pub(crate) fn set_lower(&mut self, value: Option<f32>) {
-        assert!(
-            Self::check_constraints(value, self.upper),
-            "Invalid constraint: {value:?}, self: {self:?}"
-        );
-        self.lower = value;
+        *self = Self::new(value, self.upper);
     }
     pub(crate) fn get_upper(&self) -> Option<f32> {
         self.upper
     }
     pub(crate) fn set_upper(&mut self, value: Option<f32>) {
-        assert!(
-            Self::check_constraints(self.lower, value),
-            "Invalid constraint: {value:?}, self: {self:?}"
-        );
-        self.upper = value;
+        *self = Self::new(self.lower, value);
     }
     pub(crate) fn clamp(&self, value: f32) -> f32 {
         match (self.lower, self.upper) {
@@ -68,13 +78,6 @@ impl Constraint {
             (Some(lower), Some(upper)) => value.clamp(lower, upper),
         }
     }
-    fn check_constraints(lower: Option<f32>, upper: Option<f32>) -> bool {
-        if let (Some(lower_unwrapped), Some(upper_unwrapped)) = (lower, upper) {
-            lower_unwrapped <= upper_unwrapped
-        } else {
-            true
-        }
-    }
 }
 
 impl<State> NodeValue<'_, State> {
@@ -82,6 +85,7 @@ impl<State> NodeValue<'_, State> {
         &mut self,
         available_area: Area,
         state: &mut State,
+        ctx: &mut LayoutCtx,
     ) -> Option<SizeConstraints> {
         let contextual_aligns = self.contextual_aligns();
         let allocations = self.allocate_area(
@@ -89,10 +93,11 @@ impl<State> NodeValue<'_, State> {
             contextual_aligns.0,
             contextual_aligns.1,
             state,
+            ctx,
         );
         match self {
             NodeValue::Padding { amounts, element } => element
-                .constraints(allocations[0], state)
+                .constraints(allocations[0], state, ctx)
                 .map(|constraints| SizeConstraints {
                     width: Constraint::new(
                         constraints
@@ -114,6 +119,8 @@ impl<State> NodeValue<'_, State> {
                             .get_upper()
                             .map(|upper| upper + amounts.top + amounts.bottom),
                     ),
+                    grow: constraints.grow,
+                    shrink: constraints.shrink,
                     ..Default::default()
                 }),
             NodeValue::Column {
@@ -123,7 +130,7 @@ impl<State> NodeValue<'_, State> {
             } => elements
                 .iter_mut()
                 .zip(allocations.iter())
-                .filter_map(|(element, &allocated)| element.constraints(allocated, state))
+                .filter_map(|(element, &allocated)| element.constraints(allocated, state, ctx))
                 .fold(Option::<SizeConstraints>::None, |current, constraints| {
                     if let Some(current) = current {
                         Some(SizeConstraints {
@@ -142,7 +149,7 @@ impl<State> NodeValue<'_, State> {
             } => elements
                 .iter_mut()
                 .zip(allocations.iter())
-                .filter_map(|(element, &allocated)| element.constraints(allocated, state))
+                .filter_map(|(element, &allocated)| element.constraints(allocated, state, ctx))
                 .fold(Option::<SizeConstraints>::None, |current, constraints| {
                     if let Some(current) = current {
                         Some(SizeConstraints {
@@ -154,9 +161,112 @@ impl<State> NodeValue<'_, State> {
                         Some(constraints)
                     }
                 }),
+            NodeValue::Flow {
+                elements,
+                spacing,
+                line_spacing,
+                ..
+            } => {
+                ctx.area_dependent = true;
+                let sizes: Vec<Option<SizeConstraints>> = elements
+                    .iter_mut()
+                    .map(|element| element.constraints(available_area, state, ctx))
+                    .collect();
+                // The widest element is the narrowest the flow can be,
+                // laying everything out on a single line is the widest it needs to be
+                let width = sizes.iter().flatten().fold(
+                    Option::<Constraint>::None,
+                    |current, constraints| {
+                        if let Some(current) = current {
+                            Some(Constraint::new(
+                                current
+                                    .combine_adjacent_priority(constraints.width)
+                                    .get_lower(),
+                                current.combine_sum(constraints.width, *spacing).get_upper(),
+                            ))
+                        } else {
+                            Some(constraints.width)
+                        }
+                    },
+                )?;
+                // Height depends on how elements wrap within the available width
+                let height = flow_lines(&sizes, *spacing, available_area)
+                    .iter()
+                    .map(|line| {
+                        line.items
+                            .iter()
+                            .filter_map(|&(i, _)| sizes[i].map(|size| size.height))
+                            .reduce(|current, height| current.combine_adjacent_priority(height))
+                            .unwrap_or(Constraint::none())
+                    })
+                    .reduce(|current, height| current.combine_sum(height, *line_spacing))
+                    .unwrap_or(Constraint::none());
+                Some(SizeConstraints {
+                    width,
+                    height,
+                    ..Default::default()
+                })
+            }
+            NodeValue::Grid {
+                elements,
+                cells,
+                columns,
+                rows,
+                column_gap,
+                row_gap,
+            } => Some(grid_constraints(
+                elements,
+                cells,
+                columns,
+                rows,
+                *column_gap,
+                *row_gap,
+                available_area,
+                state,
+                ctx,
+            )),
+            NodeValue::GridItem { element, .. }
+            | NodeValue::Id { element, .. }
+            | NodeValue::Direction { element, .. }
+            | NodeValue::Pointer { element, .. }
+            | NodeValue::Focusable { element, .. }
+            | NodeValue::FocusScope { element }
+            | NodeValue::Semantics { element, .. }
+            | NodeValue::Animate { element, .. }
+            | NodeValue::MatchedGeometry { element, .. } => {
+                element.constraints(allocations[0], state, ctx)
+            }
+            NodeValue::Cached { key, element, .. } => {
+                let key = ctx.cache_scope(key.get(state));
+                if let Some((constraints, area_dependent)) = ctx.cache.get(key, available_area) {
+                    // The subtree isn't measured again when it's laid out
+                    element.cache_area = Some(allocations[0]);
+                    element.cached_constraints = constraints;
+                    element.area_dependent = area_dependent;
+                    element.pass = ctx.pass;
+                    ctx.area_dependent = area_dependent;
+                    return constraints;
+                }
+                let constraints = element.constraints(allocations[0], state, ctx);
+                ctx.cache
+                    .insert(key, available_area, (constraints, element.area_dependent));
+                constraints
+            }
+            NodeValue::Custom {
+                algorithm,
+                elements,
+            } => {
+                ctx.area_dependent = true;
+                let children: Vec<Constraints> = elements
+                    .iter_mut()
+                    .filter_map(|element| element.constraints(available_area, state, ctx))
+                    .map(Constraints::from)
+                    .collect();
+                Some(algorithm.measure(available_area, &children).into())
+            }
             NodeValue::Stack { elements, .. } => elements
                 .iter_mut()
-                .filter_map(|element| element.constraints(allocations[0], state))
+                .filter_map(|element| element.constraints(allocations[0], state, ctx))
                 .fold(Option::<SizeConstraints>::None, |current, constraints| {
                     if let Some(current) = current {
                         Some(current.combine_adjacent_priority(constraints))
@@ -165,28 +275,74 @@ impl<State> NodeValue<'_, State> {
                     }
                 }),
             NodeValue::Explicit { options, element } => element
-                .constraints(allocations[0], state)
+                .constraints(allocations[0], state, ctx)
                 .map(|child_constraints| {
-                    SizeConstraints::from_size(options.clone(), allocations[0], state)
+                    ctx.area_dependent |= options.depends_on_area();
+                    SizeConstraints::from_size(options.clone(), allocations[0], state, ctx)
                         .combine_explicit_with_child(child_constraints)
                 }),
-            NodeValue::Offset { element, .. } => element.constraints(allocations[0], state),
+            NodeValue::Offset { element, .. } => element.constraints(allocations[0], state, ctx),
+            NodeValue::Scroll { axis, element, .. } => element
+                .constraints(available_area.unbounded(*axis), state, ctx)
+                .map(|constraints| {
+                    // A scroll container can be smaller than it's contents
+                    // but shouldn't be larger than them
+                    let mut scrolled = SizeConstraints {
+                        width: constraints.width,
+                        height: constraints.height,
+                        ..Default::default()
+                    };
+                    match axis {
+                        Axis::Horizontal => scrolled.width.set_lower(None),
+                        Axis::Vertical => scrolled.height.set_lower(None),
+                    }
+                    scrolled
+                }),
             NodeValue::Draw(_) => Some(SizeConstraints::default()),
+            NodeValue::VirtualList {
+                count,
+                spacing,
+                extents,
+                ..
+            } => {
+                extents.prepare(*count, *spacing, state, ctx.pass);
+                let height = extents.length(*count, *spacing);
+                Some(SizeConstraints {
+                    height: Constraint::new(Some(height), Some(height)),
+                    ..Default::default()
+                })
+            }
             NodeValue::Space | NodeValue::AreaReader { .. } => Some(SizeConstraints::default()),
-            NodeValue::Coupled { element, .. } => element.constraints(allocations[0], state),
+            NodeValue::Coupled { element, .. } => element.constraints(allocations[0], state, ctx),
             NodeValue::Visibility { visible, element } => {
                 if *visible {
-                    element.constraints(allocations[0], state)
+                    element.constraints(allocations[0], state, ctx)
                 } else {
                     None
                 }
             }
-            NodeValue::NodeTrait { node } => node.constraints(available_area, state),
-            NodeValue::Dynamic { node, computed } => computed
-                .get_or_insert(Box::new(NodeCache::new(node(state).inner)))
-                .constraints(available_area, state),
+            NodeValue::NodeTrait { node } => node.constraints(available_area, state, ctx),
+            // Nodes built in earlier passes are built again, since the state may have changed
+            NodeValue::Dynamic { node, computed } => {
+                let built = match computed.take() {
+                    Some(built) if built.is_current(ctx) => built,
+                    _ => Box::new(NodeCache::new(node(state).inner)),
+                };
+                computed
+                    .insert(built)
+                    .constraints(available_area, state, ctx)
+            }
+            NodeValue::Stateful { node, computed } => {
+                let built = match computed.take() {
+                    Some(built) if built.is_current(ctx) => built,
+                    _ => Box::new(NodeCache::new(node(&mut ctx.store, state).inner)),
+                };
+                computed
+                    .insert(built)
+                    .constraints(available_area, state, ctx)
+            }
 
-            NodeValue::Empty | NodeValue::Group(_) => unreachable!(),
+            NodeValue::Empty | NodeValue::Group(_) => None,
         }
     }
 }
@@ -197,6 +353,41 @@ impl Constraint {
     }
 }
 
+impl From<Constraint> for Bounds {
+    fn from(value: Constraint) -> Self {
+        Bounds {
+            min: value.get_lower(),
+            max: value.get_upper(),
+        }
+    }
+}
+
+impl From<Bounds> for Constraint {
+    fn from(value: Bounds) -> Self {
+        // Invalid bounds resolve in favor of the minimum
+        Constraint::new(value.min, value.max)
+    }
+}
+
+impl From<SizeConstraints> for Constraints {
+    fn from(value: SizeConstraints) -> Self {
+        Constraints {
+            width: value.width.into(),
+            height: value.height.into(),
+        }
+    }
+}
+
+impl From<Constraints> for SizeConstraints {
+    fn from(value: Constraints) -> Self {
+        SizeConstraints {
+            width: value.width.into(),
+            height: value.height.into(),
+            ..Default::default()
+        }
+    }
+}
+
 impl SizeConstraints {
     pub(crate) fn combine_adjacent_priority(self, other: Self) -> Self {
         SizeConstraints {
@@ -232,6 +423,8 @@ impl SizeConstraints {
             expand_y: self.expand_y,
             x_align: self.x_align,
             y_align: self.y_align,
+            grow: self.grow.or(child.grow),
+            shrink: self.shrink.or(child.shrink),
         }
     }
 }
@@ -295,33 +488,84 @@ impl Constraint {
 }
 
 impl SizeConstraints {
-    pub(crate) fn from_size<State>(value: Size<State>, area: Area, state: &mut State) -> Self {
+    /// Constraints from a node's size modifiers, measured in `area`.
+    ///
+    /// NaN sizes are ignored, as if they weren't set.
+    /// They're reported while the node is laid out, but not while it's measured by it's parent.
+    pub(crate) fn from_size<State>(
+        value: Size<State>,
+        area: Area,
+        state: &mut State,
+        ctx: &mut LayoutCtx,
+    ) -> Self {
+        let mut invalid = Vec::<&'static str>::new();
+        let mut valid = |source: &'static str, value: Option<f32>| match value {
+            Some(value) if value.is_nan() => {
+                invalid.push(source);
+                None
+            }
+            value => value,
+        };
         let mut initial = SizeConstraints {
-            width: if value.width_min.is_some() || value.width_max.is_some() {
-                Constraint::new(value.width_min, value.width_max)
-            } else {
-                Constraint::none()
-            },
-            height: if value.height_min.is_some() || value.height_max.is_some() {
-                Constraint::new(value.height_min, value.height_max)
-            } else {
-                Constraint::none()
-            },
-            aspect: value.aspect,
+            // Conflicting bounds are resolved in favor of the minimum & reported during layout
+            width: Bounds {
+                min: valid("width", value.width_min),
+                max: valid("width", value.width_max),
+            }
+            .into(),
+            height: Bounds {
+                min: valid("height", value.height_min),
+                max: valid("height", value.height_max),
+            }
+            .into(),
+            aspect: valid("aspect", value.aspect),
             expand_x: value.expand_x,
             expand_y: value.expand_y,
             x_align: value.x_align,
             y_align: value.y_align,
+            grow: value.grow,
+            shrink: value.shrink,
         };
         if let Some(dynamic) = value.dynamic_height {
-            let result = Some(initial.height.clamp(dynamic(area.width, state)));
-            initial.height.set_lower(result);
-            initial.height.set_upper(result);
+            if let Some(height) = valid("dynamic_height", Some(dynamic(area.width, state))) {
+                let result = Some(initial.height.clamp(height));
+                initial.height.set_lower(result);
+                initial.height.set_upper(result);
+            }
         }
         if let Some(dynamic) = value.dynamic_width {
-            let result = Some(initial.width.clamp(dynamic(area.height, state)));
-            initial.width.set_lower(result);
-            initial.width.set_upper(result);
+            if let Some(width) = valid("dynamic_width", Some(dynamic(area.height, state))) {
+                let result = Some(initial.width.clamp(width));
+                initial.width.set_lower(result);
+                initial.width.set_upper(result);
+            }
+        }
+        if let Some(measure) = value.measure {
+            // Sizes measured as NaN are treated as zero
+            let mut measure = |proposal: ProposedSize| {
+                let size = measure(proposal, state);
+                MeasuredSize {
+                    width: valid("measure", Some(size.width)).unwrap_or(0.),
+                    height: valid("measure", Some(size.height)).unwrap_or(0.),
+                }
+            };
+            let min_content = measure(ProposedSize {
+                width: Some(0.),
+                height: None,
+            });
+            let max_content = measure(ProposedSize::default());
+            let min_width = initial.width.clamp(min_content.width);
+            let max_width = initial
+                .width
+                .clamp(max_content.width.max(min_content.width));
+            let fitted = measure(ProposedSize {
+                width: Some(area.width.clamp(min_width, max_width)),
+                height: Some(area.height).filter(|height| height.is_finite()),
+            });
+            initial.width = Constraint::new(Some(min_width), Some(max_width));
+            let height = Some(initial.height.clamp(fitted.height));
+            initial.height.set_lower(height);
+            initial.height.set_upper(height);
         }
         if let Some(aspect) = initial.aspect {
             let clamped_width = initial.width.clamp((area.height * aspect).min(area.width));
@@ -329,6 +573,12 @@ impl SizeConstraints {
             let clamped_height = initial.height.clamp((area.width / aspect).min(area.height));
             initial.height.set_lower(Some(clamped_height));
         }
+        if !ctx.measuring() {
+            invalid.dedup();
+            for source in invalid {
+                ctx.diagnose(DiagnosticKind::NotANumber { source });
+            }
+        }
         initial
     }
 }
@@ -0,0 +1,329 @@
+use crate::{
+    cache::LayoutCache,
+    dump::LayoutDump,
+    focus::FocusTree,
+    models::{
+        AccessibilityNode, Area, Axis, Diagnostic, DiagnosticKind, Direction, LayoutError, NodeId,
+    },
+    store::NodeStore,
+};
+use std::{
+    collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
+};
+
+#[derive(Debug)]
+struct PathFrame {
+    kind: &'static str,
+    /// The index of the node within it's parent
+    index: Option<usize>,
+    /// The number of children visited so far
+    visited: usize,
+    /// The number of measurements started while laying out this node, see `LayoutCtx::enter_measure`
+    measured: usize,
+    /// The area which children are expected to fit within
+    bounds: Option<Area>,
+    /// The position of the node in it's parent's draw order
+    draw_rank: i64,
+    /// Whether children are drawn in the reverse of the order they are laid out in
+    reversed: bool,
+}
+
+/// A node with a pointer handler, see `Node::on_pointer`
+#[derive(Debug)]
+pub(crate) struct PointerTarget {
+    /// Identifies the node between passes, see `LayoutCtx::hovered`
+    key: u64,
+    /// The visible area of the node & it's position in draw order, if the node is visible
+    hit: Option<(Area, Vec<i64>)>,
+    /// The index of the nearest enclosing pointer target
+    parent: Option<usize>,
+}
+
+/// Results collected while laying out a tree, retained by [`crate::Layout`] between passes
+#[derive(Debug, Default)]
+pub(crate) struct LayoutCtx {
+    pub(crate) areas: HashMap<NodeId, Area>,
+    /// The visible region of the innermost scroll container being laid out
+    pub(crate) viewport: Option<Area>,
+    /// The direction of the subtree being laid out
+    pub(crate) direction: Direction,
+    pub(crate) cache: LayoutCache,
+    pub(crate) store: NodeStore,
+    /// Whether the subtree being laid out is an unchanged cached subtree,
+    /// which is laid out from the nodes built & measured in the previous pass
+    pub(crate) reusing: bool,
+    /// Incremented at the start of every pass
+    pub(crate) pass: u64,
+    /// The time passed to `Layout::layout_at`, if any
+    pub(crate) time: Option<f64>,
+    /// The progress of the innermost animation enclosing the node being laid out
+    pub(crate) transition: f32,
+    /// The combined opacity of the matched geometry nodes enclosing the node being laid out
+    pub(crate) opacity: f32,
+    /// Whether any animations were unfinished in the most recent pass
+    pub(crate) animating: bool,
+    /// Only recorded while running `Layout::debug_dump`
+    pub(crate) dump: Option<LayoutDump>,
+    pub(crate) errors: Vec<LayoutError>,
+    pub(crate) diagnostics: Vec<Diagnostic>,
+    /// Each node from the root to the node being laid out
+    path: Vec<PathFrame>,
+    /// The index of each measurement from the node being laid out to the node being measured,
+    /// along with the number of measurements started within it
+    measure_path: Vec<(usize, usize)>,
+    /// The number of measurements started before the root was laid out
+    root_measured: usize,
+    /// Whether the constraints of the node being measured depend on the area it's measured in,
+    /// see `NodeCache::area_dependent`
+    pub(crate) area_dependent: bool,
+    /// Whether the subtree being laid out is visible
+    pub(crate) visible: bool,
+    /// Visible areas of nodes with ids, along with their position in draw order
+    pub(crate) hit_areas: Vec<(NodeId, Area, Vec<i64>)>,
+    /// Nodes with pointer handlers, in the order they were laid out
+    pointer_targets: Vec<PointerTarget>,
+    /// Indices of the pointer targets enclosing the node being laid out
+    pointer_path: Vec<usize>,
+    /// Keys of the pointer targets under the pointer, retained between passes, see `Layout::dispatch`
+    pub(crate) hovered: Vec<u64>,
+    /// The focused node, retained between passes
+    pub(crate) focused: Option<NodeId>,
+    pub(crate) focus: FocusTree,
+    /// Whether the node being laid out is inside the focused node
+    pub(crate) in_focus: bool,
+    /// Nodes with semantics in the order they were laid out, along with the index of their parent
+    semantics: Vec<(Option<usize>, AccessibilityNode)>,
+    /// Indices of the nodes with semantics enclosing the node being laid out
+    semantics_path: Vec<usize>,
+}
+
+impl LayoutCtx {
+    pub(crate) fn begin_pass(&mut self, direction: Direction) {
+        self.areas.clear();
+        self.viewport = None;
+        self.direction = direction;
+        self.cache.begin_pass();
+        self.store.begin_pass();
+        self.reusing = false;
+        self.pass += 1;
+        self.transition = 1.;
+        self.opacity = 1.;
+        self.animating = false;
+        self.errors.clear();
+        self.diagnostics.clear();
+        self.path.clear();
+        self.measure_path.clear();
+        self.root_measured = 0;
+        self.visible = true;
+        self.hit_areas.clear();
+        self.pointer_targets.clear();
+        self.pointer_path.clear();
+        self.focus = FocusTree::default();
+        self.in_focus = false;
+        self.semantics.clear();
+        self.semantics_path.clear();
+    }
+    /// Tracks the path to the node being laid out,
+    /// reporting an overflow if the node is larger than it's parent's bounds.
+    ///
+    /// Must be balanced by a call to `exit_node` once the node's children are laid out.
+    pub(crate) fn enter_node(
+        &mut self,
+        kind: &'static str,
+        area: Area,
+        bounds: Option<Area>,
+        reversed: bool,
+    ) {
+        let parent = self.path.last_mut().map(|parent| {
+            parent.visited += 1;
+            (parent.visited - 1, parent.bounds, parent.reversed)
+        });
+        let index = parent.map(|(index, ..)| index);
+        self.path.push(PathFrame {
+            kind,
+            index,
+            visited: 0,
+            measured: 0,
+            bounds,
+            draw_rank: match parent {
+                // Nodes drawn in reverse are ranked from the end
+                Some((index, _, true)) => -(index as i64),
+                Some((index, _, false)) => index as i64,
+                None => 0,
+            },
+            reversed,
+        });
+        if let Some((_, Some(parent_bounds), _)) = parent {
+            self.diagnose_overflow(area, parent_bounds);
+        }
+    }
+    /// The visible portion of `area`, clipped to the current viewport,
+    /// along with the position of the node being laid out in draw order
+    fn hit_region(&self, area: Area) -> Option<(Area, Vec<i64>)> {
+        if !self.visible {
+            return None;
+        }
+        let area = match self.viewport {
+            Some(viewport) if !area.intersects(viewport) => return None,
+            Some(viewport) => area.intersection(viewport),
+            None => area,
+        };
+        Some((
+            area,
+            self.path.iter().map(|frame| frame.draw_rank).collect(),
+        ))
+    }
+    /// Records the area of the node with `id` for hit testing
+    pub(crate) fn record_hit_area(&mut self, id: NodeId, area: Area) {
+        if let Some((area, draw_order)) = self.hit_region(area) {
+            self.hit_areas.push((id, area, draw_order));
+        }
+    }
+    /// Identifies the node being laid out by it's position in the tree, which is stable between passes
+    pub(crate) fn position_key(&self) -> u64 {
+        let mut key = DefaultHasher::new();
+        for frame in &self.path {
+            frame.index.hash(&mut key);
+        }
+        key.finish()
+    }
+    /// Records a node with a pointer handler, returning it's index.
+    ///
+    /// Must be balanced by a call to `exit_pointer_target` once the node's children are laid out.
+    pub(crate) fn enter_pointer_target(&mut self, area: Area) -> usize {
+        let index = self.pointer_targets.len();
+        self.pointer_targets.push(PointerTarget {
+            key: self.position_key(),
+            hit: self.hit_region(area),
+            parent: self.pointer_path.last().copied(),
+        });
+        self.pointer_path.push(index);
+        index
+    }
+    pub(crate) fn exit_pointer_target(&mut self) {
+        self.pointer_path.pop();
+    }
+    /// The indices of the topmost pointer target containing the point & each of it's enclosing targets
+    pub(crate) fn pointer_targets_at(&self, x: f32, y: f32) -> Vec<usize> {
+        let topmost = self
+            .pointer_targets
+            .iter()
+            .enumerate()
+            .filter_map(|(index, target)| Some((index, target.hit.as_ref()?)))
+            .filter(|(_, (area, _))| area.contains(x, y))
+            .max_by(|a, b| a.1 .1.cmp(&b.1 .1))
+            .map(|(index, _)| index);
+        self.pointer_chain(topmost)
+    }
+    /// The index of `target` & each of it's enclosing targets
+    pub(crate) fn pointer_chain(&self, target: Option<usize>) -> Vec<usize> {
+        std::iter::successors(target, |index| self.pointer_targets[*index].parent).collect()
+    }
+    pub(crate) fn pointer_key(&self, target: usize) -> u64 {
+        self.pointer_targets[target].key
+    }
+    /// The index of the pointer target identified by `key` in the most recent pass
+    pub(crate) fn pointer_target(&self, key: u64) -> Option<usize> {
+        self.pointer_targets
+            .iter()
+            .position(|target| target.key == key)
+    }
+    /// Records a node with semantics.
+    ///
+    /// Must be balanced by a call to `exit_semantics` once the node's children are laid out.
+    pub(crate) fn enter_semantics(&mut self, node: AccessibilityNode) {
+        self.semantics
+            .push((self.semantics_path.last().copied(), node));
+        self.semantics_path.push(self.semantics.len() - 1);
+    }
+    pub(crate) fn exit_semantics(&mut self) {
+        self.semantics_path.pop();
+    }
+    /// The recorded nodes with semantics whose parent is `parent`, each containing their own children
+    pub(crate) fn accessibility_tree(&self, parent: Option<usize>) -> Vec<AccessibilityNode> {
+        self.semantics
+            .iter()
+            .enumerate()
+            .filter(|(_, (node_parent, _))| *node_parent == parent)
+            .map(|(index, (_, node))| AccessibilityNode {
+                children: self.accessibility_tree(Some(index)),
+                ..node.clone()
+            })
+            .collect()
+    }
+    pub(crate) fn exit_node(&mut self) {
+        self.path.pop();
+    }
+    /// Tracks the path to the node being measured.
+    ///
+    /// Must be balanced by a call to `exit_measure` once the node is measured.
+    pub(crate) fn enter_measure(&mut self) {
+        let measured = match (self.measure_path.last_mut(), self.path.last_mut()) {
+            (Some((_, measured)), _) | (None, Some(PathFrame { measured, .. })) => measured,
+            (None, None) => &mut self.root_measured,
+        };
+        *measured += 1;
+        let index = *measured - 1;
+        self.measure_path.push((index, 0));
+    }
+    pub(crate) fn exit_measure(&mut self) {
+        self.measure_path.pop();
+    }
+    /// Whether a node is being measured, rather than laid out.
+    ///
+    /// The root is measured before any node is laid out.
+    pub(crate) fn measuring(&self) -> bool {
+        !self.measure_path.is_empty() || self.path.is_empty()
+    }
+    /// Combines a cache key with the position of the node being measured,
+    /// so the same key used in different subtrees refers to different entries.
+    ///
+    /// Positions are stable between passes as long as the nodes before them are unchanged.
+    pub(crate) fn cache_scope(&self, key: u64) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        for frame in &self.path {
+            frame.index.hash(&mut hasher);
+        }
+        for (index, _) in &self.measure_path {
+            index.hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+    fn node_path(&self) -> Vec<String> {
+        self.path
+            .iter()
+            .map(|frame| match frame.index {
+                Some(index) => format!("{}[{index}]", frame.kind),
+                None => frame.kind.to_string(),
+            })
+            .collect()
+    }
+    pub(crate) fn misplaced_node(&mut self) {
+        self.errors.push(LayoutError::MisplacedNode {
+            path: self.node_path(),
+        });
+    }
+    pub(crate) fn diagnose(&mut self, kind: DiagnosticKind) {
+        self.diagnostics.push(Diagnostic {
+            path: self.node_path(),
+            kind,
+        });
+    }
+    pub(crate) fn diagnose_overflow(&mut self, area: Area, available_area: Area) {
+        let axes = [
+            (Axis::Horizontal, area.width, available_area.width),
+            (Axis::Vertical, area.height, available_area.height),
+        ];
+        for (axis, size, available) in axes {
+            if size - available > 0.01 {
+                self.diagnose(DiagnosticKind::Overflow {
+                    axis,
+                    size,
+                    available,
+                });
+            }
+        }
+    }
+}
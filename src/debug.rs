@@ -1,4 +1,4 @@
-use crate::layout::NodeValue;
+use crate::{cache::CacheKey, layout::NodeValue};
 use std::fmt;
 
 impl<State> fmt::Debug for NodeValue<'_, State> {
@@ -43,6 +43,45 @@ impl<State> fmt::Debug for NodeValue<'_, State> {
                 .field("x_align", x_align)
                 .field("y_align", y_align)
                 .finish(),
+            NodeValue::Custom { elements, .. } => f
+                .debug_struct("Custom")
+                .field("elements", elements)
+                .finish(),
+            NodeValue::Flow {
+                elements,
+                spacing,
+                line_spacing,
+                align,
+                off_axis_align,
+            } => f
+                .debug_struct("Flow")
+                .field("elements", elements)
+                .field("spacing", spacing)
+                .field("line_spacing", line_spacing)
+                .field("align", align)
+                .field("off_axis_align", off_axis_align)
+                .finish(),
+            NodeValue::Grid {
+                elements,
+                cells,
+                columns,
+                rows,
+                column_gap,
+                row_gap,
+            } => f
+                .debug_struct("Grid")
+                .field("elements", elements)
+                .field("cells", cells)
+                .field("columns", columns)
+                .field("rows", rows)
+                .field("column_gap", column_gap)
+                .field("row_gap", row_gap)
+                .finish(),
+            NodeValue::GridItem { placement, element } => f
+                .debug_struct("GridItem")
+                .field("placement", placement)
+                .field("element", element)
+                .finish(),
             NodeValue::Group(elements) => f.debug_tuple("Group").field(elements).finish(),
             NodeValue::Offset {
                 offset_x,
@@ -62,7 +101,10 @@ impl<State> fmt::Debug for NodeValue<'_, State> {
                 .finish(),
             NodeValue::Space => write!(f, "Space"),
             NodeValue::Empty => write!(f, "Empty"),
-            NodeValue::AreaReader { .. } => write!(f, "WidthReader"),
+            NodeValue::AreaReader { computed, .. } => f
+                .debug_struct("AreaReader")
+                .field("computed", computed)
+                .finish(),
             NodeValue::Coupled {
                 element,
                 coupled,
@@ -79,10 +121,345 @@ impl<State> fmt::Debug for NodeValue<'_, State> {
                 .field("element", &element)
                 .field("visible", visible)
                 .finish(),
+            NodeValue::Scroll {
+                axis,
+                offset,
+                element,
+                ..
+            } => f
+                .debug_struct("Scroll")
+                .field("axis", axis)
+                .field("offset", offset)
+                .field("element", element)
+                .finish(),
+            NodeValue::VirtualList {
+                count,
+                spacing,
+                align,
+                off_axis_align,
+                computed,
+                ..
+            } => f
+                .debug_struct("VirtualList")
+                .field("count", count)
+                .field("spacing", spacing)
+                .field("align", align)
+                .field("off_axis_align", off_axis_align)
+                .field("computed", computed)
+                .finish(),
+            NodeValue::Direction { direction, element } => f
+                .debug_struct("Direction")
+                .field("direction", direction)
+                .field("element", element)
+                .finish(),
+            NodeValue::Cached {
+                key,
+                element,
+                laid_out,
+            } => f
+                .debug_struct("Cached")
+                .field("key", key)
+                .field("element", element)
+                .field("laid_out", laid_out)
+                .finish(),
+            NodeValue::Id { id, element, .. } => f
+                .debug_struct("Id")
+                .field("id", id)
+                .field("element", element)
+                .finish(),
+            NodeValue::Focusable {
+                id,
+                tab_index,
+                neighbors,
+                element,
+            } => f
+                .debug_struct("Focusable")
+                .field("id", id)
+                .field("tab_index", tab_index)
+                .field("neighbors", neighbors)
+                .field("element", element)
+                .finish(),
+            NodeValue::FocusScope { element } => f
+                .debug_struct("FocusScope")
+                .field("element", element)
+                .finish(),
+            NodeValue::Semantics {
+                role,
+                label,
+                value,
+                element,
+            } => f
+                .debug_struct("Semantics")
+                .field("role", role)
+                .field("label", label)
+                .field("value", value)
+                .field("element", element)
+                .finish(),
+            NodeValue::Animate {
+                key,
+                curve,
+                duration,
+                element,
+            } => f
+                .debug_struct("Animate")
+                .field("key", key)
+                .field("curve", curve)
+                .field("duration", duration)
+                .field("element", element)
+                .finish(),
+            NodeValue::MatchedGeometry {
+                key,
+                curve,
+                duration,
+                element,
+            } => f
+                .debug_struct("MatchedGeometry")
+                .field("key", key)
+                .field("curve", curve)
+                .field("duration", duration)
+                .field("element", element)
+                .finish(),
+            NodeValue::Pointer {
+                element, target, ..
+            } => f
+                .debug_struct("Pointer")
+                .field("handler", &"<function>")
+                .field("element", element)
+                .field("target", target)
+                .finish(),
             NodeValue::Dynamic { computed, .. } => f
                 .debug_struct("Dynamic")
                 .field("computed", computed)
                 .finish(),
+            NodeValue::Stateful { computed, .. } => f
+                .debug_struct("Stateful")
+                .field("computed", computed)
+                .finish(),
+        }
+    }
+}
+
+impl<State> NodeValue<'_, State> {
+    /// The kind of node & the values of it's modifiers, used by `Layout::debug_dump`
+    pub(crate) fn dump_description(&self) -> (&'static str, Vec<(&'static str, String)>) {
+        let mut modifiers = Vec::<(&'static str, String)>::new();
+        let mut some = |name: &'static str, value: Option<String>| {
+            if let Some(value) = value {
+                modifiers.push((name, value));
+            }
+        };
+        match self {
+            NodeValue::Padding { amounts, .. } => {
+                some("leading", Some(amounts.leading.to_string()));
+                some("trailing", Some(amounts.trailing.to_string()));
+                some("top", Some(amounts.top.to_string()));
+                some("bottom", Some(amounts.bottom.to_string()));
+            }
+            NodeValue::Column {
+                spacing,
+                align,
+                off_axis_align,
+                ..
+            } => {
+                some("spacing", Some(spacing.to_string()));
+                some("align", align.map(|align| format!("{align:?}")));
+                some(
+                    "off_axis_align",
+                    off_axis_align.map(|align| format!("{align:?}")),
+                );
+            }
+            NodeValue::Row {
+                spacing,
+                align,
+                off_axis_align,
+                ..
+            } => {
+                some("spacing", Some(spacing.to_string()));
+                some("align", align.map(|align| format!("{align:?}")));
+                some(
+                    "off_axis_align",
+                    off_axis_align.map(|align| format!("{align:?}")),
+                );
+            }
+            NodeValue::Stack {
+                x_align, y_align, ..
+            } => {
+                some("x_align", x_align.map(|align| format!("{align:?}")));
+                some("y_align", y_align.map(|align| format!("{align:?}")));
+            }
+            NodeValue::Flow {
+                spacing,
+                line_spacing,
+                align,
+                off_axis_align,
+                ..
+            } => {
+                some("spacing", Some(spacing.to_string()));
+                some("line_spacing", Some(line_spacing.to_string()));
+                some("align", align.map(|align| format!("{align:?}")));
+                some(
+                    "off_axis_align",
+                    off_axis_align.map(|align| format!("{align:?}")),
+                );
+            }
+            NodeValue::Grid {
+                columns,
+                rows,
+                column_gap,
+                row_gap,
+                ..
+            } => {
+                some("columns", Some(format!("{columns:?}")));
+                some("rows", Some(format!("{rows:?}")));
+                some("column_gap", Some(column_gap.to_string()));
+                some("row_gap", Some(row_gap.to_string()));
+            }
+            NodeValue::GridItem { placement, .. } => {
+                some("column", placement.column.map(|value| value.to_string()));
+                some("row", placement.row.map(|value| value.to_string()));
+                some(
+                    "column_span",
+                    placement.column_span.map(|value| value.to_string()),
+                );
+                some(
+                    "row_span",
+                    placement.row_span.map(|value| value.to_string()),
+                );
+            }
+            NodeValue::Offset {
+                offset_x, offset_y, ..
+            } => {
+                some("offset_x", Some(offset_x.to_string()));
+                some("offset_y", Some(offset_y.to_string()));
+            }
+            NodeValue::Explicit { options, .. } => {
+                some(
+                    "width_min",
+                    options.width_min.map(|value| value.to_string()),
+                );
+                some(
+                    "width_max",
+                    options.width_max.map(|value| value.to_string()),
+                );
+                some(
+                    "height_min",
+                    options.height_min.map(|value| value.to_string()),
+                );
+                some(
+                    "height_max",
+                    options.height_max.map(|value| value.to_string()),
+                );
+                some("x_align", options.x_align.map(|align| format!("{align:?}")));
+                some("y_align", options.y_align.map(|align| format!("{align:?}")));
+                some("aspect", options.aspect.map(|value| value.to_string()));
+                some("expand_x", options.expand_x.then(|| true.to_string()));
+                some("expand_y", options.expand_y.then(|| true.to_string()));
+                some("grow", options.grow.map(|value| value.to_string()));
+                some("shrink", options.shrink.map(|value| value.to_string()));
+            }
+            NodeValue::Coupled { over, .. } => {
+                some("over", Some(over.to_string()));
+            }
+            NodeValue::Visibility { visible, .. } => {
+                some("visible", Some(visible.to_string()));
+            }
+            NodeValue::Id { id, .. } => {
+                some("id", Some(format!("{id:?}")));
+            }
+            NodeValue::Focusable { id, tab_index, .. } => {
+                some("id", Some(format!("{id:?}")));
+                some("tab_index", tab_index.map(|index| index.to_string()));
+            }
+            NodeValue::Semantics {
+                role, label, value, ..
+            } => {
+                some("role", Some(format!("{role:?}")));
+                some("label", Some(format!("{label:?}")));
+                some("value", value.as_ref().map(|value| format!("{value:?}")));
+            }
+            NodeValue::Animate {
+                key,
+                curve,
+                duration,
+                ..
+            }
+            | NodeValue::MatchedGeometry {
+                key,
+                curve,
+                duration,
+                ..
+            } => {
+                some("key", Some(format!("{key:?}")));
+                some("curve", Some(format!("{curve:?}")));
+                some("duration", Some(duration.to_string()));
+            }
+            NodeValue::Scroll { axis, offset, .. } => {
+                some("axis", Some(format!("{axis:?}")));
+                some("offset", Some(offset.to_string()));
+            }
+            NodeValue::Direction { direction, .. } => {
+                some("direction", Some(format!("{direction:?}")));
+            }
+            NodeValue::Cached { key, laid_out, .. } => {
+                let key = match key {
+                    CacheKey::Fixed(key) => Some(*key),
+                    CacheKey::Computed(_) => laid_out.map(|(key, _)| key),
+                };
+                some("key", key.map(|key| key.to_string()));
+            }
+            NodeValue::VirtualList {
+                count,
+                spacing,
+                align,
+                off_axis_align,
+                ..
+            } => {
+                some("count", Some(count.to_string()));
+                some("spacing", Some(spacing.to_string()));
+                some("align", align.map(|align| format!("{align:?}")));
+                some(
+                    "off_axis_align",
+                    off_axis_align.map(|align| format!("{align:?}")),
+                );
+            }
+            _ => {}
+        }
+        (self.kind_name(), modifiers)
+    }
+    /// The name of the node's kind, used to describe nodes in diagnostics & `Layout::debug_dump`
+    pub(crate) fn kind_name(&self) -> &'static str {
+        match self {
+            NodeValue::Padding { .. } => "Padding",
+            NodeValue::Column { .. } => "Column",
+            NodeValue::Row { .. } => "Row",
+            NodeValue::Stack { .. } => "Stack",
+            NodeValue::Custom { .. } => "Custom",
+            NodeValue::Flow { .. } => "Flow",
+            NodeValue::Grid { .. } => "Grid",
+            NodeValue::GridItem { .. } => "GridItem",
+            NodeValue::Group(_) => "Group",
+            NodeValue::Offset { .. } => "Offset",
+            NodeValue::Draw(_) => "Draw",
+            NodeValue::Explicit { .. } => "Explicit",
+            NodeValue::Empty => "Empty",
+            NodeValue::Space => "Space",
+            NodeValue::AreaReader { .. } => "AreaReader",
+            NodeValue::Coupled { .. } => "Coupled",
+            NodeValue::Visibility { .. } => "Visibility",
+            NodeValue::NodeTrait { .. } => "NodeTrait",
+            NodeValue::Dynamic { .. } => "Dynamic",
+            NodeValue::Stateful { .. } => "Stateful",
+            NodeValue::Id { .. } => "Id",
+            NodeValue::Scroll { .. } => "Scroll",
+            NodeValue::Direction { .. } => "Direction",
+            NodeValue::Cached { .. } => "Cached",
+            NodeValue::VirtualList { .. } => "VirtualList",
+            NodeValue::Pointer { .. } => "Pointer",
+            NodeValue::Focusable { .. } => "Focusable",
+            NodeValue::FocusScope { .. } => "FocusScope",
+            NodeValue::Semantics { .. } => "Semantics",
+            NodeValue::Animate { .. } => "Animate",
+            NodeValue::MatchedGeometry { .. } => "MatchedGeometry",
         }
     }
 }
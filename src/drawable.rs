@@ -1,35 +1,64 @@
-use crate::{models::Area, traits::Drawable};
+use crate::{
+    models::{Area, DrawCtx},
+    traits::{Drawable, TransitionDrawable},
+};
 use std::fmt;
 
 type DrawFn<'nodes, State> = Box<dyn Fn(Area, &mut State) + 'nodes>;
+type CtxDrawFn<'nodes, State> = Box<dyn Fn(Area, &mut State, DrawCtx) + 'nodes>;
 
 pub(crate) enum SomeDrawable<'nodes, State> {
     Fn(DrawFn<'nodes, State>),
+    CtxFn(CtxDrawFn<'nodes, State>),
     Object(Box<dyn Drawable<State> + 'nodes>),
+    Transition(Box<dyn TransitionDrawable<State> + 'nodes>),
 }
 
 impl<State> SomeDrawable<'_, State> {
-    fn draw(&mut self, area: Area, state: &mut State, visible: bool) {
+    fn draw(&mut self, area: Area, state: &mut State, ctx: DrawCtx) {
         match self {
             SomeDrawable::Fn(closure) => {
-                if visible {
+                if ctx.visible {
                     closure(area, state)
                 }
             }
-            SomeDrawable::Object(object) => object.draw(area, state, visible),
+            SomeDrawable::CtxFn(closure) => {
+                if ctx.visible {
+                    closure(area, state, ctx)
+                }
+            }
+            SomeDrawable::Object(object) => object.draw_with_ctx(area, state, ctx),
+            SomeDrawable::Transition(object) => object.draw_with_ctx(area, state, ctx),
         }
     }
 }
 
 pub(crate) struct DrawableNode<'nodes, State> {
     pub(crate) area: Area,
+    /// Whether the node was laid out outside the viewport of a scroll container
+    pub(crate) clipped: bool,
+    /// Whether the node was laid out inside the focused node
+    pub(crate) focused: bool,
+    /// The progress of the innermost enclosing animation
+    pub(crate) progress: f32,
+    /// The combined opacity of the enclosing matched geometry nodes
+    pub(crate) opacity: f32,
     pub(crate) drawable: SomeDrawable<'nodes, State>,
 }
 
 impl<State> DrawableNode<'_, State> {
     pub(crate) fn draw(&mut self, area: Area, state: &mut State, contextual_visibility: bool) {
         if area.width >= 0. && area.height >= 0. {
-            self.drawable.draw(area, state, contextual_visibility);
+            self.drawable.draw(
+                area,
+                state,
+                DrawCtx {
+                    visible: contextual_visibility && !self.clipped,
+                    focused: self.focused,
+                    progress: self.progress,
+                    opacity: self.opacity,
+                },
+            );
         }
     }
 }
@@ -38,6 +67,10 @@ impl<State> fmt::Debug for DrawableNode<'_, State> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Drawable")
             .field("area", &self.area)
+            .field("clipped", &self.clipped)
+            .field("focused", &self.focused)
+            .field("progress", &self.progress)
+            .field("opacity", &self.opacity)
             .field("draw", &"<function>")
             .finish()
     }
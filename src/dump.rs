@@ -0,0 +1,185 @@
+use crate::{
+    constraints::{Constraint, SizeConstraints},
+    layout::NodeValue,
+    models::Area,
+};
+use std::fmt::Write;
+
+/// A node as it was laid out, see `Layout::debug_dump`
+#[derive(Debug)]
+struct DumpEntry {
+    depth: usize,
+    kind: &'static str,
+    modifiers: Vec<(&'static str, String)>,
+    constraints: Option<SizeConstraints>,
+    area: Area,
+}
+
+/// Records every node visited during a layout pass
+#[derive(Debug, Default)]
+pub(crate) struct LayoutDump {
+    entries: Vec<DumpEntry>,
+    depth: usize,
+    /// The constraints of the node which is about to be laid out
+    pub(crate) pending_constraints: Option<SizeConstraints>,
+}
+
+impl LayoutDump {
+    pub(crate) fn enter<State>(&mut self, node: &NodeValue<'_, State>, area: Area) {
+        let (kind, modifiers) = node.dump_description();
+        self.entries.push(DumpEntry {
+            depth: self.depth,
+            kind,
+            modifiers,
+            constraints: self.pending_constraints.take(),
+            area,
+        });
+        self.depth += 1;
+    }
+    pub(crate) fn exit(&mut self) {
+        self.depth -= 1;
+    }
+    pub(crate) fn text(&self) -> String {
+        let mut text = String::new();
+        for entry in &self.entries {
+            let _ = write!(text, "{}{}", "  ".repeat(entry.depth), entry.kind);
+            if !entry.modifiers.is_empty() {
+                let modifiers: Vec<String> = entry
+                    .modifiers
+                    .iter()
+                    .map(|(name, value)| format!("{name}: {value}"))
+                    .collect();
+                let _ = write!(text, " {{ {} }}", modifiers.join(", "));
+            }
+            match entry.constraints {
+                Some(constraints) => {
+                    let _ = write!(
+                        text,
+                        " constraints: {{ width: {}, height: {}",
+                        constraint_text(constraints.width),
+                        constraint_text(constraints.height)
+                    );
+                    if let Some(aspect) = constraints.aspect {
+                        let _ = write!(text, ", aspect: {aspect}");
+                    }
+                    text.push_str(" }");
+                }
+                None => text.push_str(" constraints: none"),
+            }
+            let Area {
+                x,
+                y,
+                width,
+                height,
+            } = entry.area;
+            let _ = writeln!(
+                text,
+                " area: {{ x: {x}, y: {y}, width: {width}, height: {height} }}"
+            );
+        }
+        text
+    }
+    #[cfg(feature = "json")]
+    pub(crate) fn json(&self) -> String {
+        let mut json = String::new();
+        let mut open = 0;
+        for (i, entry) in self.entries.iter().enumerate() {
+            if i > 0 {
+                if entry.depth > self.entries[i - 1].depth {
+                    json.push_str(",\"children\":[");
+                } else {
+                    json.push('}');
+                    for _ in entry.depth..self.entries[i - 1].depth {
+                        json.push_str("]}");
+                    }
+                    json.push(',');
+                }
+            }
+            open = entry.depth;
+            let _ = write!(
+                json,
+                "{{\"kind\":{},\"modifiers\":{{",
+                json_string(entry.kind)
+            );
+            for (i, (name, value)) in entry.modifiers.iter().enumerate() {
+                if i > 0 {
+                    json.push(',');
+                }
+                let _ = write!(json, "{}:{}", json_string(name), json_string(value));
+            }
+            json.push_str("},\"constraints\":");
+            match entry.constraints {
+                Some(constraints) => {
+                    let _ = write!(
+                        json,
+                        "{{\"width\":{},\"height\":{},\"aspect\":{}}}",
+                        json_constraint(constraints.width),
+                        json_constraint(constraints.height),
+                        json_number(constraints.aspect)
+                    );
+                }
+                None => json.push_str("null"),
+            }
+            let _ = write!(
+                json,
+                ",\"area\":{{\"x\":{},\"y\":{},\"width\":{},\"height\":{}}}",
+                json_number(Some(entry.area.x)),
+                json_number(Some(entry.area.y)),
+                json_number(Some(entry.area.width)),
+                json_number(Some(entry.area.height))
+            );
+        }
+        if !self.entries.is_empty() {
+            json.push('}');
+            for _ in 0..open {
+                json.push_str("]}");
+            }
+        }
+        json
+    }
+}
+
+fn constraint_text(constraint: Constraint) -> String {
+    match (constraint.get_lower(), constraint.get_upper()) {
+        (Some(lower), Some(upper)) => format!("{lower}..={upper}"),
+        (Some(lower), None) => format!("{lower}.."),
+        (None, Some(upper)) => format!("..={upper}"),
+        (None, None) => "..".to_string(),
+    }
+}
+
+#[cfg(feature = "json")]
+fn json_constraint(constraint: Constraint) -> String {
+    format!(
+        "{{\"lower\":{},\"upper\":{}}}",
+        json_number(constraint.get_lower()),
+        json_number(constraint.get_upper())
+    )
+}
+
+#[cfg(feature = "json")]
+fn json_number(value: Option<f32>) -> String {
+    match value {
+        Some(value) if value.is_finite() => format!("{value}"),
+        _ => "null".to_string(),
+    }
+}
+
+#[cfg(feature = "json")]
+fn json_string(value: &str) -> String {
+    let mut json = String::with_capacity(value.len() + 2);
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
@@ -0,0 +1,102 @@
+use crate::{
+    layout::NodeValue,
+    models::{PointerEvent, Propagation},
+    node_cache::NodeCache,
+};
+
+impl<State> NodeValue<'_, State> {
+    /// Delivers `event` to the pointer targets in `receivers`, innermost first,
+    /// until a handler stops propagation.
+    ///
+    /// `targets` must be a chain of enclosing pointer targets which contains every receiver,
+    /// see `LayoutCtx::pointer_targets_at`
+    pub(crate) fn dispatch(
+        &mut self,
+        event: PointerEvent,
+        targets: &[usize],
+        receivers: &[usize],
+        state: &mut State,
+    ) -> Propagation {
+        match self {
+            NodeValue::Pointer {
+                handler,
+                element,
+                target,
+            } => {
+                let Some((index, area)) = *target else {
+                    return Propagation::Continue;
+                };
+                // Targets are always enclosed by the rest of the chain,
+                // so the subtree of a node outside of the chain can't contain targets
+                if !targets.contains(&index) {
+                    return Propagation::Continue;
+                }
+                match element.kind.dispatch(event, targets, receivers, state) {
+                    Propagation::Continue if receivers.contains(&index) => {
+                        handler(event, area, state)
+                    }
+                    propagation => propagation,
+                }
+            }
+            NodeValue::Padding { element, .. }
+            | NodeValue::Explicit { element, .. }
+            | NodeValue::Offset { element, .. }
+            | NodeValue::GridItem { element, .. }
+            | NodeValue::Id { element, .. }
+            | NodeValue::Scroll { element, .. }
+            | NodeValue::Direction { element, .. }
+            | NodeValue::Cached { element, .. }
+            | NodeValue::Visibility { element, .. }
+            | NodeValue::Focusable { element, .. }
+            | NodeValue::FocusScope { element }
+            | NodeValue::Semantics { element, .. }
+            | NodeValue::Animate { element, .. }
+            | NodeValue::MatchedGeometry { element, .. } => {
+                element.kind.dispatch(event, targets, receivers, state)
+            }
+            NodeValue::Column { elements, .. }
+            | NodeValue::Row { elements, .. }
+            | NodeValue::Stack { elements, .. }
+            | NodeValue::Flow { elements, .. }
+            | NodeValue::Grid { elements, .. }
+            | NodeValue::Custom { elements, .. }
+            | NodeValue::VirtualList {
+                computed: elements, ..
+            } => dispatch_all(elements.iter_mut(), event, targets, receivers, state),
+            NodeValue::Coupled {
+                element, coupled, ..
+            } => dispatch_all(
+                [element.as_mut(), coupled.as_mut()].into_iter(),
+                event,
+                targets,
+                receivers,
+                state,
+            ),
+            NodeValue::Dynamic { computed, .. }
+            | NodeValue::Stateful { computed, .. }
+            | NodeValue::AreaReader { computed, .. } => match computed {
+                Some(computed) => computed.kind.dispatch(event, targets, receivers, state),
+                None => Propagation::Continue,
+            },
+            NodeValue::NodeTrait { node } => node.dispatch(event, targets, receivers, state),
+            NodeValue::Draw(_) | NodeValue::Space | NodeValue::Group(_) | NodeValue::Empty => {
+                Propagation::Continue
+            }
+        }
+    }
+}
+
+fn dispatch_all<'a, 'nodes: 'a, State: 'a>(
+    elements: impl Iterator<Item = &'a mut NodeCache<'nodes, State>>,
+    event: PointerEvent,
+    targets: &[usize],
+    receivers: &[usize],
+    state: &mut State,
+) -> Propagation {
+    for element in elements {
+        if element.kind.dispatch(event, targets, receivers, state) == Propagation::Stop {
+            return Propagation::Stop;
+        }
+    }
+    Propagation::Continue
+}
@@ -0,0 +1,214 @@
+use crate::models::{Area, NavigationDirection, NodeId};
+
+/// How much distance in the direction of navigation outweighs distance along the other axis.
+///
+/// Squared distances are compared, so distance in the direction of navigation counts about 3.6 times as much.
+/// A node in the next row or column is preferred over a better aligned node further away,
+/// so navigation doesn't skip over nearby nodes which are slightly offset.
+const DIRECTION_WEIGHT: f32 = 13.;
+
+#[derive(Debug)]
+struct Focusable {
+    id: NodeId,
+    tab_index: Option<u32>,
+    area: Area,
+    neighbors: Vec<(NavigationDirection, NodeId)>,
+    /// The innermost focus scope containing the node
+    scope: usize,
+}
+
+#[derive(Debug)]
+struct FocusScope {
+    parent: Option<usize>,
+    /// The number of focusable nodes recorded before the scope was entered,
+    /// used to position the scope in it's parent's tree order
+    position: usize,
+}
+
+/// The focusable nodes & focus scopes found in a layout pass, see `Node::focusable`
+///
+/// The root of the tree is always scope `0`.
+#[derive(Debug)]
+pub(crate) struct FocusTree {
+    focusables: Vec<Focusable>,
+    scopes: Vec<FocusScope>,
+    /// The scopes enclosing the node being laid out
+    path: Vec<usize>,
+}
+
+impl Default for FocusTree {
+    fn default() -> Self {
+        Self {
+            focusables: Vec::new(),
+            scopes: vec![FocusScope {
+                parent: None,
+                position: 0,
+            }],
+            path: vec![0],
+        }
+    }
+}
+
+impl FocusTree {
+    pub(crate) fn record_focusable(
+        &mut self,
+        id: NodeId,
+        tab_index: Option<u32>,
+        area: Area,
+        neighbors: Vec<(NavigationDirection, NodeId)>,
+    ) {
+        self.focusables.push(Focusable {
+            id,
+            tab_index,
+            area,
+            neighbors,
+            scope: self.current_scope(),
+        });
+    }
+    pub(crate) fn enter_scope(&mut self) {
+        self.scopes.push(FocusScope {
+            parent: Some(self.current_scope()),
+            position: self.focusables.len(),
+        });
+        self.path.push(self.scopes.len() - 1);
+    }
+    pub(crate) fn exit_scope(&mut self) {
+        self.path.pop();
+    }
+    fn current_scope(&self) -> usize {
+        self.path.last().copied().unwrap_or(0)
+    }
+    /// The tab order of every focusable node within `scope`.
+    ///
+    /// Within each scope, nodes with a tab index come first in ascending order,
+    /// followed by the remaining nodes & nested scopes in tree order.
+    fn tab_order(&self, scope: usize) -> Vec<NodeId> {
+        // Scopes are placed before the focusable nodes recorded after they were entered
+        #[derive(PartialEq, Eq, PartialOrd, Ord)]
+        enum Item {
+            Scope(usize),
+            Focusable(usize),
+        }
+        let mut items: Vec<(Option<u32>, usize, Item)> = self
+            .focusables
+            .iter()
+            .enumerate()
+            .filter(|(_, focusable)| focusable.scope == scope)
+            .map(|(index, focusable)| (focusable.tab_index, index, Item::Focusable(index)))
+            .chain(
+                self.scopes
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, nested)| nested.parent == Some(scope))
+                    .map(|(index, nested)| (None, nested.position, Item::Scope(index))),
+            )
+            .collect();
+        items.sort_by(|a, b| (a.0.is_none(), a.0, a.1, &a.2).cmp(&(b.0.is_none(), b.0, b.1, &b.2)));
+        items
+            .into_iter()
+            .flat_map(|(.., item)| match item {
+                Item::Focusable(index) => vec![self.focusables[index].id],
+                Item::Scope(index) => self.tab_order(index),
+            })
+            .collect()
+    }
+    /// The node after (or before) `focused` in tab order, wrapping around at the ends.
+    ///
+    /// Focus stays within the innermost focus scope containing `focused`.
+    /// If nothing is focused, or the focused node no longer exists,
+    /// the first (or last) node in the tree is returned.
+    pub(crate) fn step(&self, focused: Option<NodeId>, forward: bool) -> Option<NodeId> {
+        let current = focused.and_then(|focused| {
+            self.focusables
+                .iter()
+                .find(|focusable| focusable.id == focused)
+        });
+        let order = self.tab_order(current.map_or(0, |focusable| focusable.scope));
+        let position = current.and_then(|current| order.iter().position(|id| *id == current.id));
+        let next = match (position, forward) {
+            (None, true) => 0,
+            (None, false) => order.len().checked_sub(1)?,
+            (Some(position), true) => (position + 1) % order.len(),
+            (Some(position), false) => (position + order.len() - 1) % order.len(),
+        };
+        order.get(next).copied()
+    }
+    /// Whether `scope` is `ancestor` or is nested within it
+    fn scope_within(&self, scope: usize, ancestor: usize) -> bool {
+        std::iter::successors(Some(scope), |scope| self.scopes[*scope].parent)
+            .any(|scope| scope == ancestor)
+    }
+    /// The nearest node to `focused` in `direction`, see `Layout::navigate`
+    pub(crate) fn navigate(
+        &self,
+        focused: Option<NodeId>,
+        direction: NavigationDirection,
+    ) -> Option<NodeId> {
+        let Some(current) = focused.and_then(|focused| {
+            self.focusables
+                .iter()
+                .find(|focusable| focusable.id == focused)
+        }) else {
+            return self.step(None, true);
+        };
+        let candidates = self.focusables.iter().filter(|candidate| {
+            candidate.id != current.id && self.scope_within(candidate.scope, current.scope)
+        });
+        if let Some((_, neighbor)) = current
+            .neighbors
+            .iter()
+            .rev()
+            .find(|(neighbor_direction, _)| *neighbor_direction == direction)
+        {
+            if candidates
+                .clone()
+                .any(|candidate| candidate.id == *neighbor)
+            {
+                return Some(*neighbor);
+            }
+        }
+        // Measures each area along the axis of movement & the other axis,
+        // flipped so that `direction` is always increasing
+        let axes = |area: Area| {
+            let (start, end, cross_start, cross_end) = match direction {
+                NavigationDirection::Left | NavigationDirection::Right => {
+                    (area.x, area.x + area.width, area.y, area.y + area.height)
+                }
+                NavigationDirection::Up | NavigationDirection::Down => {
+                    (area.y, area.y + area.height, area.x, area.x + area.width)
+                }
+            };
+            match direction {
+                NavigationDirection::Right | NavigationDirection::Down => {
+                    (start, end, cross_start, cross_end)
+                }
+                NavigationDirection::Left | NavigationDirection::Up => {
+                    (-end, -start, cross_start, cross_end)
+                }
+            }
+        };
+        let (start, end, cross_start, cross_end) = axes(current.area);
+        let center = (start + end) * 0.5;
+        let cross_center = (cross_start + cross_end) * 0.5;
+        candidates
+            .filter_map(|candidate| {
+                let (candidate_start, candidate_end, candidate_cross_start, candidate_cross_end) =
+                    axes(candidate.area);
+                // Candidates must lie beyond the focused node, rather than containing it
+                if (candidate_start + candidate_end) * 0.5 <= center || candidate_start < start {
+                    return None;
+                }
+                let overlaps =
+                    candidate_cross_start.max(cross_start) < candidate_cross_end.min(cross_end);
+                let distance = (candidate_start - end).max(0.);
+                let cross_distance =
+                    ((candidate_cross_start + candidate_cross_end) * 0.5 - cross_center).abs();
+                let score =
+                    DIRECTION_WEIGHT * distance * distance + cross_distance * cross_distance;
+                Some(((!overlaps, score), candidate.id))
+            })
+            .min_by(|(a, _), (b, _)| a.0.cmp(&b.0).then(a.1.total_cmp(&b.1)))
+            .map(|(_, id)| id)
+            .or(Some(current.id))
+    }
+}
@@ -0,0 +1,334 @@
+use crate::{
+    constraints::{Constraint, SizeConstraints},
+    context::LayoutCtx,
+    layout::Orientation,
+    models::{Area, GridPlacement, Track, XAlign, YAlign},
+    node_cache::NodeCache,
+};
+use std::collections::HashSet;
+
+/// A resolved cell position in a grid
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct GridCell {
+    pub(crate) column: usize,
+    pub(crate) row: usize,
+    pub(crate) column_span: usize,
+    pub(crate) row_span: usize,
+}
+
+impl GridCell {
+    fn start(&self, orientation: Orientation) -> usize {
+        match orientation {
+            Orientation::Horizontal => self.column,
+            Orientation::Vertical => self.row,
+        }
+    }
+    fn span(&self, orientation: Orientation) -> usize {
+        match orientation {
+            Orientation::Horizontal => self.column_span,
+            Orientation::Vertical => self.row_span,
+        }
+    }
+}
+
+/// Resolves explicitly placed cells, then auto-places the remaining cells in row-major order.
+///
+/// Returns the resolved cells & the number of rows required to fit them.
+pub(crate) fn place_cells(
+    placements: &[GridPlacement],
+    column_count: usize,
+    row_count: usize,
+) -> (Vec<GridCell>, usize) {
+    let column_count = column_count.max(1);
+    // Row spans beyond this can't be filled by the other cells, so they're clamped to it
+    let span_limit = row_count.max(1) + placements.len();
+    let mut occupied = HashSet::<(usize, usize)>::new();
+    let fits = |occupied: &HashSet<(usize, usize)>, cell: &GridCell| {
+        (cell.column..cell.column + cell.column_span)
+            .all(|c| (cell.row..cell.row + cell.row_span).all(|r| !occupied.contains(&(c, r))))
+    };
+    let occupy = |occupied: &mut HashSet<(usize, usize)>, cell: &GridCell| {
+        for c in cell.column..cell.column + cell.column_span {
+            for r in cell.row..cell.row + cell.row_span {
+                occupied.insert((c, r));
+            }
+        }
+    };
+
+    let mut cells = vec![Option::<GridCell>::None; placements.len()];
+    for (i, placement) in placements.iter().enumerate() {
+        if let (Some(column), Some(row)) = (placement.column, placement.row) {
+            let column = column.min(column_count - 1);
+            let cell = GridCell {
+                column,
+                row,
+                column_span: placement
+                    .column_span
+                    .unwrap_or(1)
+                    .clamp(1, column_count - column),
+                row_span: placement.row_span.unwrap_or(1).clamp(1, span_limit),
+            };
+            occupy(&mut occupied, &cell);
+            cells[i] = Some(cell);
+        }
+    }
+
+    let mut cursor = 0;
+    for (i, placement) in placements.iter().enumerate() {
+        if cells[i].is_some() {
+            continue;
+        }
+        let column_span = placement.column_span.unwrap_or(1).clamp(1, column_count);
+        let row_span = placement.row_span.unwrap_or(1).clamp(1, span_limit);
+        loop {
+            let cell = GridCell {
+                column: cursor % column_count,
+                row: cursor / column_count,
+                column_span,
+                row_span,
+            };
+            if cell.column + column_span <= column_count && fits(&occupied, &cell) {
+                occupy(&mut occupied, &cell);
+                cells[i] = Some(cell);
+                cursor += column_span;
+                break;
+            }
+            cursor += 1;
+        }
+    }
+
+    let cells: Vec<GridCell> = cells.into_iter().flatten().collect();
+    let rows = cells
+        .iter()
+        .map(|cell| cell.row + cell.row_span)
+        .max()
+        .unwrap_or(0)
+        .max(row_count);
+    (cells, rows)
+}
+
+/// Combines each track's sizing rule with the constraints of the cells it contains.
+///
+/// Only cells which span a single track contribute to the size of that track.
+fn resolve_tracks(
+    tracks: &[Track],
+    cells: &[GridCell],
+    sizes: &[Option<SizeConstraints>],
+    orientation: Orientation,
+) -> Vec<(Constraint, f32)> {
+    let mut contents = vec![Option::<Constraint>::None; tracks.len()];
+    for (cell, size) in cells.iter().zip(sizes) {
+        let Some(size) = size else {
+            continue;
+        };
+        if cell.span(orientation) != 1 {
+            continue;
+        }
+        let constraint = match orientation {
+            Orientation::Horizontal => size.width,
+            Orientation::Vertical => size.height,
+        };
+        let Some(content) = contents.get_mut(cell.start(orientation)) else {
+            continue;
+        };
+        *content = Some(match content {
+            Some(current) => current.combine_adjacent_priority(constraint),
+            None => constraint,
+        });
+    }
+    tracks
+        .iter()
+        .zip(contents)
+        .map(|(track, content)| match *track {
+            Track::Fixed(size) => (Constraint::new(Some(size), Some(size)), 1.),
+            Track::Range { min, max } => {
+                // Content bounds are respected, as long as they fall within the track's bounds
+                let content = content.unwrap_or(Constraint::none());
+                let lower = match (min, content.get_lower()) {
+                    (Some(min), Some(content)) => Some(min.max(content)),
+                    (min, content) => min.or(content),
+                }
+                .map(|lower| max.map_or(lower, |max| lower.min(max)));
+                let upper = max.or(content
+                    .get_upper()
+                    .map(|upper| upper.max(lower.unwrap_or(upper))));
+                (Constraint::new(lower, upper), 1.)
+            }
+            Track::Fraction(weight) => (
+                Constraint::new(content.and_then(|content| content.get_lower()), None),
+                weight.max(0.),
+            ),
+            Track::Fit => (content.unwrap_or(Constraint::new(Some(0.), Some(0.))), 1.),
+        })
+        .collect()
+}
+
+/// Distributes `available` space between weighted, constrained items.
+///
+/// Every item grows in proportion to it's weight until it reaches it's upper bound,
+/// and never shrinks below it's lower bound.
+pub(crate) fn distribute(available: f32, items: &[(Constraint, f32)]) -> Vec<f32> {
+    let lower = |constraint: &Constraint| constraint.get_lower().unwrap_or(0.);
+    let upper = |constraint: &Constraint| {
+        constraint
+            .get_upper()
+            .unwrap_or(f32::INFINITY)
+            .max(lower(constraint))
+    };
+    // Each item starts growing once the fill level passes lower / weight
+    // and stops once the fill level passes upper / weight
+    let mut events = Vec::<(f32, f32)>::new();
+    for (constraint, weight) in items {
+        if *weight > 0. {
+            events.push((lower(constraint) / weight, *weight));
+            if upper(constraint).is_finite() {
+                events.push((upper(constraint) / weight, -weight));
+            }
+        }
+    }
+    events.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut total: f32 = items.iter().map(|(constraint, _)| lower(constraint)).sum();
+    let mut level = 0.;
+    let mut slope = 0.;
+    let mut solved = None;
+    if total >= available {
+        solved = Some(0.);
+    } else {
+        for (at, delta) in events {
+            let next = total + slope * (at - level);
+            if slope > 0. && next >= available {
+                solved = Some(level + (available - total) / slope);
+                break;
+            }
+            total = next;
+            level = at;
+            slope += delta;
+        }
+        if solved.is_none() && slope > 0. {
+            solved = Some(level + (available - total) / slope);
+        }
+    }
+    let level = solved.unwrap_or(f32::INFINITY);
+    items
+        .iter()
+        .map(|(constraint, weight)| {
+            if *weight > 0. {
+                (weight * level).clamp(lower(constraint), upper(constraint))
+            } else {
+                lower(constraint)
+            }
+        })
+        .collect()
+}
+
+fn sum_tracks(tracks: &[(Constraint, f32)], gap: f32) -> Constraint {
+    tracks
+        .iter()
+        .map(|(constraint, _)| *constraint)
+        .reduce(|current, constraint| current.combine_sum(constraint, gap))
+        .unwrap_or(Constraint::none())
+}
+
+fn track_positions(sizes: &[f32], gap: f32, start: f32, available: f32) -> Vec<f32> {
+    let total = sizes.iter().sum::<f32>() + gap * (sizes.len() as i32 - 1).max(0) as f32;
+    let mut current = start + ((available - total) * 0.5).max(0.);
+    sizes
+        .iter()
+        .map(|size| {
+            let position = current;
+            current += size + gap;
+            position
+        })
+        .collect()
+}
+
+fn span_size(sizes: &[f32], start: usize, span: usize, gap: f32) -> f32 {
+    sizes[start.min(sizes.len())..(start + span).min(sizes.len())]
+        .iter()
+        .sum::<f32>()
+        + gap * (span - 1) as f32
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn grid_constraints<State>(
+    elements: &mut [NodeCache<'_, State>],
+    cells: &[GridCell],
+    columns: &[Track],
+    rows: &[Track],
+    column_gap: f32,
+    row_gap: f32,
+    available_area: Area,
+    state: &mut State,
+    ctx: &mut LayoutCtx,
+) -> SizeConstraints {
+    let sizes: Vec<Option<SizeConstraints>> = elements
+        .iter_mut()
+        .map(|element| element.constraints(available_area, state, ctx))
+        .collect();
+    SizeConstraints {
+        width: sum_tracks(
+            &resolve_tracks(columns, cells, &sizes, Orientation::Horizontal),
+            column_gap,
+        ),
+        height: sum_tracks(
+            &resolve_tracks(rows, cells, &sizes, Orientation::Vertical),
+            row_gap,
+        ),
+        ..Default::default()
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn layout_grid<State>(
+    elements: &mut [NodeCache<'_, State>],
+    cells: &[GridCell],
+    columns: &[Track],
+    rows: &[Track],
+    column_gap: f32,
+    row_gap: f32,
+    available_area: Area,
+    state: &mut State,
+    ctx: &mut LayoutCtx,
+) -> Vec<Area> {
+    let sizes: Vec<Option<SizeConstraints>> = elements
+        .iter_mut()
+        .map(|element| element.constraints(available_area, state, ctx))
+        .collect();
+    let column_sizes = distribute(
+        available_area.width - column_gap * (columns.len() as i32 - 1).max(0) as f32,
+        &resolve_tracks(columns, cells, &sizes, Orientation::Horizontal),
+    );
+    let row_sizes = distribute(
+        available_area.height - row_gap * (rows.len() as i32 - 1).max(0) as f32,
+        &resolve_tracks(rows, cells, &sizes, Orientation::Vertical),
+    );
+    let column_positions = track_positions(
+        &column_sizes,
+        column_gap,
+        available_area.x,
+        available_area.width,
+    );
+    let row_positions =
+        track_positions(&row_sizes, row_gap, available_area.y, available_area.height);
+    cells
+        .iter()
+        .zip(sizes)
+        .map(|(cell, size)| {
+            // Cells are placed within the grid's tracks, see `place_cells`
+            Area {
+                x: column_positions
+                    .get(cell.column)
+                    .copied()
+                    .unwrap_or(available_area.x),
+                y: row_positions
+                    .get(cell.row)
+                    .copied()
+                    .unwrap_or(available_area.y),
+                width: span_size(&column_sizes, cell.column, cell.column_span, column_gap),
+                height: span_size(&row_sizes, cell.row, cell.row_span, row_gap),
+            }
+            .constrained(&size.unwrap_or_default(), XAlign::Center, YAlign::Center)
+        })
+        .collect()
+}
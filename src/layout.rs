@@ -1,9 +1,20 @@
 use crate::{
-    constraints::SizeConstraints, drawable::DrawableNode, models::*, node_cache::NodeCache,
-    traits::NodeTrait, Node,
+    cache::{CacheKey, LayoutCache},
+    constraints::{Constraint, SizeConstraints},
+    context::LayoutCtx,
+    drawable::DrawableNode,
+    dump::LayoutDump,
+    grid::{distribute, layout_grid, GridCell},
+    lifecycle::{fire_disappeared, DisappearFns, Lifecycle},
+    models::*,
+    node_cache::NodeCache,
+    store::{ComponentStore, NodeStore},
+    traits::{LayoutAlgorithm, NodeTrait},
+    virtual_list::ItemExtents,
+    Node,
 };
 use core::f32;
-use std::fmt::Debug;
+use std::{fmt::Debug, hash::Hash, mem};
 
 /**
 The root object used to store & calculate a layout
@@ -44,35 +55,341 @@ struct MyState {}
  */
 pub struct Layout<'nodes, State> {
     tree: Node<'nodes, State>,
+    ctx: LayoutCtx,
+    laid_out: bool,
+    direction: Direction,
+    /// `Node::on_disappear` callbacks found in the most recent layout pass, see [`ComponentStore`]
+    on_disappear: DisappearFns<State>,
 }
 
 impl<'nodes, State> Layout<'nodes, State> {
     /// Creates a new [`Layout<State>`].
     pub fn new(tree: Node<'nodes, State>) -> Self {
-        Self { tree }
+        Self {
+            tree,
+            ctx: LayoutCtx::default(),
+            laid_out: false,
+            direction: Direction::default(),
+            on_disappear: Vec::new(),
+        }
+    }
+    /// Sets the horizontal direction of the layout, which defaults to [`Direction::LeftToRight`]
+    ///
+    /// The direction can be overridden for a subtree using [`Node::direction`]
+    pub fn with_direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+    /// Restores focus retained from a previous layout, see [`Layout::focused`]
+    pub fn with_focus(mut self, focused: Option<NodeId>) -> Self {
+        self.ctx.focused = focused;
+        self
+    }
+    /// Uses a [`LayoutCache`] retained from a previous layout, see [`Node::cache_key`]
+    pub fn with_cache(mut self, cache: LayoutCache) -> Self {
+        self.ctx.cache = cache;
+        self
+    }
+    /// Uses a [`ComponentStore`] retained from a previous layout, see [`crate::nodes::stateful`]
+    pub fn with_store(mut self, store: ComponentStore<State>) -> Self {
+        self.ctx.store = store.nodes;
+        self.on_disappear = store.on_disappear;
+        self
     }
 }
 
 impl<State> Layout<'_, State> {
     /// Calculates layout and draws all draw nodes in the tree
+    ///
+    /// Equivalent to calling [`Layout::layout`] followed by [`Layout::draw_only`]
     pub fn draw(&mut self, area: Area, state: &mut State) {
-        let constraints = self.tree.inner.constraints(area, state);
-        self.tree.inner.layout(
-            area.constrained(
-                &constraints.unwrap_or_default(),
-                XAlign::Center,
-                YAlign::Center,
-            ),
-            None,
-            None,
-            state,
+        self.layout(area, state);
+        self.draw_only(state);
+    }
+    /// Calculates layout at `time` & draws all draw nodes in the tree
+    ///
+    /// Equivalent to calling [`Layout::layout_at`] followed by [`Layout::draw_only`]
+    pub fn draw_at(&mut self, area: Area, time: f64, state: &mut State) {
+        self.layout_at(area, time, state);
+        self.draw_only(state);
+    }
+    /// Calculates layout & draws all draw nodes in the tree, unless the tree is invalid.
+    ///
+    /// Nothing is drawn if an error is returned.
+    /// Recoverable problems don't prevent drawing, they are reported by [`Layout::diagnostics`]
+    pub fn try_draw(&mut self, area: Area, state: &mut State) -> Result<(), LayoutError> {
+        self.layout(area, state);
+        if let Some(error) = self.ctx.errors.first() {
+            return Err(error.clone());
+        }
+        self.draw_only(state);
+        Ok(())
+    }
+    /// Returns the recoverable problems found in the most recent layout pass,
+    /// see [`DiagnosticKind`] for how each problem is resolved.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.ctx.diagnostics
+    }
+    /// Takes the [`LayoutCache`] so that it can be passed to the next layout using [`Layout::with_cache`]
+    pub fn take_cache(&mut self) -> LayoutCache {
+        std::mem::take(&mut self.ctx.cache)
+    }
+    /// Takes the [`ComponentStore`] so that it can be passed to the next layout using [`Layout::with_store`]
+    pub fn take_store(&mut self) -> ComponentStore<State> {
+        ComponentStore {
+            nodes: std::mem::take(&mut self.ctx.store),
+            on_disappear: std::mem::take(&mut self.on_disappear),
+        }
+    }
+    /// Calculates layout without drawing.
+    ///
+    /// This is the only phase which calls `dynamic` & `area_reader` closures.
+    /// The resulting areas of nodes marked with [`Node::id`] can be read with [`Layout::area_of`]
+    pub fn layout(&mut self, area: Area, state: &mut State) {
+        self.layout_pass(area, None, state);
+    }
+    /// Calculates layout without drawing, animating nodes marked with [`Node::animate`].
+    ///
+    /// `time` is read from a clock supplied by the caller, in seconds.
+    /// Animations finish immediately when laid out with [`Layout::layout`] instead.
+    pub fn layout_at(&mut self, area: Area, time: f64, state: &mut State) {
+        self.layout_pass(area, Some(time), state);
+    }
+    /// Returns true if any animations were unfinished in the most recent layout pass,
+    /// in which case another pass should be scheduled.
+    pub fn is_animating(&self) -> bool {
+        self.ctx.animating
+    }
+    /// Returns the nodes tagged with [`Node::matched_geometry`] which weren't found in a recent layout pass
+    /// & are still fading out, in no particular order.
+    ///
+    /// The nodes themselves are no longer in the tree, so they're drawn by the caller.
+    pub fn departures(&self) -> Vec<Departure> {
+        self.ctx.store.departures()
+    }
+    fn layout_pass(&mut self, area: Area, time: Option<f64>, state: &mut State) {
+        self.ctx.time = time;
+        self.ctx.begin_pass(self.direction);
+        let constraints = self.tree.inner.constraints(area, state, &mut self.ctx);
+        if let Some(dump) = &mut self.ctx.dump {
+            dump.pending_constraints = constraints;
+        }
+        let mut root_area = area.constrained(
+            &constraints.unwrap_or_default(),
+            XAlign::Center,
+            YAlign::Center,
         );
-        self.tree.inner.draw(state, true);
+        // Like any other allocation, the root is mirrored within the available area
+        if self.direction == Direction::RightToLeft {
+            root_area = root_area.mirrored(area);
+        }
+        self.ctx
+            .enter_node(self.tree.inner.kind_name(), area, Some(area), false);
+        self.ctx.diagnose_overflow(root_area, area);
+        self.ctx.exit_node();
+        self.tree
+            .inner
+            .layout(root_area, None, None, state, &mut self.ctx);
+        self.ctx.store.end_pass(self.ctx.time);
+        self.ctx.animating |= !self.ctx.store.departures().is_empty();
+        self.laid_out = true;
+        self.fire_lifecycle(state);
+    }
+    /// Runs a layout pass at the time of the most recent pass, recording it for `Layout::debug_dump`
+    fn dump_pass(&mut self, area: Area, state: &mut State) -> LayoutDump {
+        self.ctx.dump = Some(LayoutDump::default());
+        self.layout_pass(area, self.ctx.time, state);
+        self.ctx.dump.take().unwrap_or_default()
+    }
+    /// Calls the lifecycle callbacks of nodes whose ids were added or removed in the most recent pass
+    fn fire_lifecycle(&mut self, state: &mut State) {
+        self.ctx
+            .store
+            .replace_ids(self.ctx.areas.keys().copied().collect());
+        fire_disappeared(mem::take(&mut self.on_disappear), state, &self.ctx);
+        self.tree
+            .inner
+            .fire_lifecycle(state, &mut self.ctx, &mut self.on_disappear);
+    }
+    /// Draws all draw nodes in the tree using the results of the most recent [`Layout::layout`]
+    ///
+    /// Layout is not recalculated, so this can be called multiple times per layout pass
+    /// (for example, once for a shadow pass & once for a visual pass).
+    /// Does nothing if the layout has not been calculated yet.
+    pub fn draw_only(&mut self, state: &mut State) {
+        if self.laid_out {
+            self.tree.inner.draw(state, true);
+        }
+    }
+    /// Returns the ids of nodes which contain the point, using the results of the most recent layout pass.
+    ///
+    /// Ids are ordered topmost-first, matching the order nodes are drawn in.
+    /// Nodes in `visible(false)` subtrees & areas clipped by a scroll container are excluded.
+    ///
+    /// See [`Node::id`]
+    pub fn hit_test(&self, x: f32, y: f32) -> Vec<NodeId> {
+        let mut hits: Vec<&(NodeId, Area, Vec<i64>)> = self
+            .ctx
+            .hit_areas
+            .iter()
+            .filter(|(_, area, _)| area.contains(x, y))
+            .collect();
+        hits.sort_by(|a, b| b.2.cmp(&a.2));
+        hits.into_iter().map(|(id, ..)| *id).collect()
+    }
+    /// Delivers a pointer event to the topmost node with a [`Node::on_pointer`] handler
+    /// which contains the event's position, using the results of the most recent layout pass.
+    ///
+    /// The event then bubbles up through each enclosing handler until one returns [`Propagation::Stop`].
+    /// Nodes in `visible(false)` subtrees & areas clipped by a scroll container don't receive events.
+    ///
+    /// The nodes under the pointer are tracked between events. Before an event is delivered,
+    /// handlers which the pointer has left receive [`PointerEvent::Leave`] & handlers which it has entered
+    /// receive [`PointerEvent::Enter`], neither of which bubble.
+    /// Dispatch `Enter` & `Leave` when the pointer enters or leaves the window,
+    /// they're only delivered to the nodes the pointer enters or leaves.
+    ///
+    /// Returns [`Propagation::Stop`] if a handler stopped the event.
+    pub fn dispatch(&mut self, event: PointerEvent, state: &mut State) -> Propagation {
+        if !self.laid_out {
+            return Propagation::Continue;
+        }
+        let (x, y) = event.position();
+        let targets = match event {
+            PointerEvent::Leave { .. } => Vec::new(),
+            _ => self.ctx.pointer_targets_at(x, y),
+        };
+        self.update_hover(&targets, x, y, state);
+        match event {
+            PointerEvent::Enter { .. } | PointerEvent::Leave { .. } => Propagation::Continue,
+            _ if targets.is_empty() => Propagation::Continue,
+            _ => self.tree.inner.dispatch(event, &targets, &targets, state),
+        }
+    }
+    /// Delivers `Leave` to the hovered targets which aren't in `targets`, innermost first,
+    /// then `Enter` to the targets which weren't hovered, outermost first
+    fn update_hover(&mut self, targets: &[usize], x: f32, y: f32, state: &mut State) {
+        let hovered: Vec<u64> = targets
+            .iter()
+            .map(|target| self.ctx.pointer_key(*target))
+            .collect();
+        let previous = mem::replace(&mut self.ctx.hovered, hovered.clone());
+        for key in previous.iter().filter(|key| !hovered.contains(key)) {
+            // Targets which were removed by a layout pass have nothing to deliver to
+            if let Some(target) = self.ctx.pointer_target(*key) {
+                let chain = self.ctx.pointer_chain(Some(target));
+                self.tree
+                    .inner
+                    .dispatch(PointerEvent::Leave { x, y }, &chain, &[target], state);
+            }
+        }
+        for (target, key) in targets.iter().zip(&hovered).rev() {
+            if !previous.contains(key) {
+                let chain = self.ctx.pointer_chain(Some(*target));
+                self.tree
+                    .inner
+                    .dispatch(PointerEvent::Enter { x, y }, &chain, &[*target], state);
+            }
+        }
+    }
+    /// Returns the focused node, see [`Node::focusable`]
+    pub fn focused(&self) -> Option<NodeId> {
+        self.ctx.focused
+    }
+    /// Focuses the node marked with `focusable(id)`
+    ///
+    /// Drawables see the new focus after the next layout pass.
+    pub fn focus(&mut self, id: impl Hash) {
+        self.ctx.focused = Some(NodeId::new(id));
+    }
+    /// Removes focus from the focused node
+    ///
+    /// Drawables see the change after the next layout pass.
+    pub fn clear_focus(&mut self) {
+        self.ctx.focused = None;
+    }
+    /// Moves focus to the next node in tab order & returns it, using the results of the most recent layout pass.
+    ///
+    /// Tab order is tree order, except that nodes with a tab index come first, see [`Node::focusable_with`].
+    /// Focus wraps around at the end of the order & never leaves the innermost [`Node::focus_scope`]
+    /// containing the focused node.
+    ///
+    /// Drawables see the new focus after the next layout pass.
+    pub fn focus_next(&mut self) -> Option<NodeId> {
+        self.ctx.focused = self.ctx.focus.step(self.ctx.focused, true);
+        self.ctx.focused
+    }
+    /// Moves focus to the previous node in tab order & returns it, see [`Layout::focus_next`]
+    ///
+    /// Drawables see the new focus after the next layout pass.
+    pub fn focus_prev(&mut self) -> Option<NodeId> {
+        self.ctx.focused = self.ctx.focus.step(self.ctx.focused, false);
+        self.ctx.focused
+    }
+    /// Moves focus to the nearest focusable node in `direction` & returns it,
+    /// using the areas calculated in the most recent layout pass.
+    ///
+    /// Nodes which overlap the focused node along the other axis are preferred,
+    /// followed by the closest node, where distance in `direction` outweighs distance along the other axis.
+    /// Neighbors set with [`Node::focusable_with`] take precedence over geometry.
+    ///
+    /// Focus is unchanged if there is no node in `direction`,
+    /// & never leaves the innermost [`Node::focus_scope`] containing the focused node.
+    /// If nothing is focused, the first node in tab order is focused.
+    ///
+    /// Drawables see the new focus after the next layout pass.
+    pub fn navigate(&mut self, direction: NavigationDirection) -> Option<NodeId> {
+        self.ctx.focused = self.ctx.focus.navigate(self.ctx.focused, direction);
+        self.ctx.focused
+    }
+    /// Returns the nodes marked with [`Node::semantics`], using the results of the most recent layout pass.
+    ///
+    /// Each node contains the nodes with semantics inside of it, in tree order.
+    /// Nodes in `visible(false)` subtrees are excluded.
+    pub fn accessibility_tree(&self) -> Vec<AccessibilityNode> {
+        self.ctx.accessibility_tree(None)
+    }
+    /// Calculates layout without drawing & returns an indented tree of every node that was laid out.
+    ///
+    /// Each line shows the kind of node, it's modifiers, the constraints used to size it,
+    /// and the area it was laid out in.
+    /// ```text
+    /// Column { spacing: 0 } constraints: { width: .., height: 10.. } area: { x: 0, y: 0, width: 100, height: 100 }
+    ///   Explicit { height_min: 10, height_max: 10 } constraints: { width: .., height: 10..=10 } area: { x: 0, y: 0, width: 100, height: 10 }
+    ///     Space constraints: { width: .., height: .. } area: { x: 0, y: 0, width: 100, height: 10 }
+    ///   Space constraints: { width: .., height: .. } area: { x: 0, y: 10, width: 100, height: 90 }
+    /// ```
+    ///
+    /// Nodes which were laid out without being measured, such as the contents of an `area_reader`, show no constraints.
+    ///
+    /// The dump is recorded during an ordinary layout pass at the time of the most recent pass,
+    /// so areas, hit testing & drawing afterwards use the dumped layout.
+    pub fn debug_dump(&mut self, area: Area, state: &mut State) -> String {
+        self.dump_pass(area, state).text()
+    }
+    /// Equivalent to [`Layout::debug_dump`], formatted as JSON.
+    ///
+    /// Each node is an object with `kind`, `modifiers`, `constraints`, `area` & `children` (if any) fields.
+    /// Unbounded constraints are `null`.
+    #[cfg(feature = "json")]
+    pub fn debug_dump_json(&mut self, area: Area, state: &mut State) -> String {
+        self.dump_pass(area, state).json()
+    }
+    /// Returns the area calculated in the most recent layout pass for the node marked with `id`
+    ///
+    /// See [`Node::id`]
+    pub fn area_of(&self, id: impl Hash) -> Option<Area> {
+        self.ctx.areas.get(&NodeId::new(id)).copied()
     }
 }
 
 type AreaReaderFn<'nodes, State> = Box<dyn Fn(Area, &mut State) -> Node<'nodes, State> + 'nodes>;
 type DynamicNodeFn<'nodes, State> = Box<dyn Fn(&mut State) -> Node<'nodes, State> + 'nodes>;
+type StatefulNodeFn<'nodes, State> =
+    Box<dyn Fn(&mut NodeStore, &mut State) -> Node<'nodes, State> + 'nodes>;
+type ContentSizeFn<'nodes, State> = Box<dyn Fn(f32, f32, &mut State) + 'nodes>;
+type ItemBuilderFn<'nodes, State> = Box<dyn Fn(usize, &mut State) -> Node<'nodes, State> + 'nodes>;
+type PointerFn<'nodes, State> = Box<dyn Fn(PointerEvent, Area, &mut State) -> Propagation + 'nodes>;
 
 pub(crate) enum NodeValue<'nodes, State> {
     Padding {
@@ -96,6 +413,29 @@ pub(crate) enum NodeValue<'nodes, State> {
         x_align: Option<XAlign>,
         y_align: Option<YAlign>,
     },
+    Custom {
+        algorithm: Box<dyn LayoutAlgorithm + 'nodes>,
+        elements: Vec<NodeCache<'nodes, State>>,
+    },
+    Flow {
+        elements: Vec<NodeCache<'nodes, State>>,
+        spacing: f32,
+        line_spacing: f32,
+        align: Option<XAlign>,
+        off_axis_align: Option<YAlign>,
+    },
+    Grid {
+        elements: Vec<NodeCache<'nodes, State>>,
+        cells: Vec<GridCell>,
+        columns: Vec<Track>,
+        rows: Vec<Track>,
+        column_gap: f32,
+        row_gap: f32,
+    },
+    GridItem {
+        placement: GridPlacement,
+        element: Box<NodeCache<'nodes, State>>,
+    },
     Group(Vec<NodeCache<'nodes, State>>),
     Offset {
         offset_x: f32,
@@ -111,6 +451,7 @@ pub(crate) enum NodeValue<'nodes, State> {
     Space,
     AreaReader {
         read: AreaReaderFn<'nodes, State>,
+        computed: Option<Box<NodeCache<'nodes, State>>>,
     },
     Coupled {
         over: bool,
@@ -128,6 +469,74 @@ pub(crate) enum NodeValue<'nodes, State> {
         node: DynamicNodeFn<'nodes, State>,
         computed: Option<Box<NodeCache<'nodes, State>>>,
     },
+    Stateful {
+        node: StatefulNodeFn<'nodes, State>,
+        computed: Option<Box<NodeCache<'nodes, State>>>,
+    },
+    Id {
+        id: NodeId,
+        lifecycle: Lifecycle<'nodes, State>,
+        element: Box<NodeCache<'nodes, State>>,
+    },
+    Scroll {
+        axis: Axis,
+        offset: f32,
+        element: Box<NodeCache<'nodes, State>>,
+        on_content_size: Option<ContentSizeFn<'nodes, State>>,
+    },
+    Direction {
+        direction: Direction,
+        element: Box<NodeCache<'nodes, State>>,
+    },
+    Cached {
+        key: CacheKey<'nodes, State>,
+        element: Box<NodeCache<'nodes, State>>,
+        /// The key & area the subtree was laid out with in the previous pass
+        laid_out: Option<(u64, Area)>,
+    },
+    Focusable {
+        id: NodeId,
+        tab_index: Option<u32>,
+        /// Explicit neighbors for `Layout::navigate`, later entries take precedence
+        neighbors: Vec<(NavigationDirection, NodeId)>,
+        element: Box<NodeCache<'nodes, State>>,
+    },
+    FocusScope {
+        element: Box<NodeCache<'nodes, State>>,
+    },
+    Semantics {
+        role: Role,
+        label: String,
+        value: Option<String>,
+        element: Box<NodeCache<'nodes, State>>,
+    },
+    Animate {
+        key: NodeId,
+        curve: Curve,
+        duration: f32,
+        element: Box<NodeCache<'nodes, State>>,
+    },
+    MatchedGeometry {
+        key: NodeId,
+        curve: Curve,
+        duration: f32,
+        element: Box<NodeCache<'nodes, State>>,
+    },
+    Pointer {
+        handler: PointerFn<'nodes, State>,
+        element: Box<NodeCache<'nodes, State>>,
+        /// The index of this node's pointer target & it's area, from the most recent layout pass
+        target: Option<(usize, Area)>,
+    },
+    VirtualList {
+        count: usize,
+        spacing: f32,
+        extents: ItemExtents<'nodes, State>,
+        builder: ItemBuilderFn<'nodes, State>,
+        align: Option<YAlign>,
+        off_axis_align: Option<XAlign>,
+        computed: Vec<NodeCache<'nodes, State>>,
+    },
 }
 
 impl<State> NodeValue<'_, State> {
@@ -136,15 +545,33 @@ impl<State> NodeValue<'_, State> {
             NodeValue::Draw(drawable) => drawable.draw(drawable.area, state, contextual_visibility),
             NodeValue::Padding { element, .. }
             | NodeValue::Explicit { element, .. }
-            | NodeValue::Offset { element, .. } => {
+            | NodeValue::Offset { element, .. }
+            | NodeValue::GridItem { element, .. }
+            | NodeValue::Id { element, .. }
+            | NodeValue::Scroll { element, .. }
+            | NodeValue::Direction { element, .. }
+            | NodeValue::Cached { element, .. }
+            | NodeValue::Pointer { element, .. }
+            | NodeValue::Focusable { element, .. }
+            | NodeValue::FocusScope { element }
+            | NodeValue::Semantics { element, .. }
+            | NodeValue::Animate { element, .. }
+            | NodeValue::MatchedGeometry { element, .. } => {
                 element.draw(state, contextual_visibility);
             }
-            NodeValue::Stack { elements, .. } => {
+            NodeValue::Stack { elements, .. }
+            | NodeValue::Grid { elements, .. }
+            | NodeValue::Custom { elements, .. } => {
                 elements
                     .iter_mut()
                     .for_each(|el| el.draw(state, contextual_visibility));
             }
-            NodeValue::Column { elements, .. } | NodeValue::Row { elements, .. } => {
+            NodeValue::Column { elements, .. }
+            | NodeValue::Row { elements, .. }
+            | NodeValue::Flow { elements, .. }
+            | NodeValue::VirtualList {
+                computed: elements, ..
+            } => {
                 elements
                     .iter_mut()
                     .rev()
@@ -170,13 +597,15 @@ impl<State> NodeValue<'_, State> {
             Self::NodeTrait { node } => {
                 node.draw(state, contextual_visibility);
             }
-            NodeValue::Dynamic { computed, .. } => computed
-                .as_mut()
-                .unwrap()
-                .draw(state, contextual_visibility),
-            NodeValue::Group(_) | NodeValue::Empty | NodeValue::AreaReader { .. } => {
-                unreachable!()
+            NodeValue::Dynamic { computed, .. }
+            | NodeValue::Stateful { computed, .. }
+            | NodeValue::AreaReader { computed, .. } => {
+                if let Some(computed) = computed {
+                    computed.draw(state, contextual_visibility)
+                }
             }
+            // Misplaced nodes are reported during layout & never drawn
+            NodeValue::Group(_) | NodeValue::Empty => (),
         }
     }
 
@@ -186,10 +615,20 @@ impl<State> NodeValue<'_, State> {
             off_axis_align: x,
             ..
         }
+        | NodeValue::VirtualList {
+            align: y,
+            off_axis_align: x,
+            ..
+        }
         | NodeValue::Row {
             align: x,
             off_axis_align: y,
             ..
+        }
+        | NodeValue::Flow {
+            align: x,
+            off_axis_align: y,
+            ..
         } = self
         {
             (*x, *y)
@@ -204,6 +643,7 @@ impl<State> NodeValue<'_, State> {
         contextual_x_align: Option<XAlign>,
         contextual_y_align: Option<YAlign>,
         state: &mut State,
+        ctx: &mut LayoutCtx,
     ) -> Vec<Area> {
         match self {
             NodeValue::Padding { amounts, .. } => vec![Area {
@@ -225,7 +665,7 @@ impl<State> NodeValue<'_, State> {
                 off_axis_align.unwrap_or(XAlign::Center),
                 align.unwrap_or(YAlign::Center),
                 state,
-                true,
+                ctx,
             ),
             NodeValue::Row {
                 elements,
@@ -240,7 +680,41 @@ impl<State> NodeValue<'_, State> {
                 align.unwrap_or(XAlign::Center),
                 off_axis_align.unwrap_or(YAlign::Center),
                 state,
-                true,
+                ctx,
+            ),
+            NodeValue::Flow {
+                elements,
+                spacing,
+                line_spacing,
+                align,
+                off_axis_align,
+            } => layout_flow(
+                elements,
+                *spacing,
+                *line_spacing,
+                available_area,
+                align.unwrap_or(XAlign::Center),
+                off_axis_align.unwrap_or(YAlign::Center),
+                state,
+                ctx,
+            ),
+            NodeValue::Grid {
+                elements,
+                cells,
+                columns,
+                rows,
+                column_gap,
+                row_gap,
+            } => layout_grid(
+                elements,
+                cells,
+                columns,
+                rows,
+                *column_gap,
+                *row_gap,
+                available_area,
+                state,
+                ctx,
             ),
             NodeValue::Stack {
                 elements,
@@ -248,7 +722,7 @@ impl<State> NodeValue<'_, State> {
                 y_align,
             } => elements
                 .iter_mut()
-                .filter_map(|element| element.constraints(available_area, state))
+                .filter_map(|element| element.constraints(available_area, state, ctx))
                 .map(|constraints| {
                     available_area.constrained(
                         &constraints,
@@ -257,9 +731,38 @@ impl<State> NodeValue<'_, State> {
                     )
                 })
                 .collect(),
+            NodeValue::Custom {
+                algorithm,
+                elements,
+            } => {
+                let sizes: Vec<Option<Constraints>> = elements
+                    .iter_mut()
+                    .map(|element| {
+                        element
+                            .constraints(available_area, state, ctx)
+                            .map(Constraints::from)
+                    })
+                    .collect();
+                // Hidden elements aren't passed to the algorithm
+                let children: Vec<Constraints> = sizes.iter().flatten().copied().collect();
+                let mut placed = algorithm.place(available_area, &children).into_iter();
+                let empty = Area {
+                    x: available_area.x,
+                    y: available_area.y,
+                    width: 0.,
+                    height: 0.,
+                };
+                sizes
+                    .iter()
+                    .map(|size| match size {
+                        Some(_) => placed.next().unwrap_or(empty),
+                        None => empty,
+                    })
+                    .collect()
+            }
             NodeValue::Explicit { options, .. } => {
                 vec![available_area.constrained(
-                    &SizeConstraints::from_size(options.clone(), available_area, state),
+                    &SizeConstraints::from_size(options.clone(), available_area, state, ctx),
                     contextual_x_align.unwrap_or(XAlign::Center),
                     contextual_y_align.unwrap_or(YAlign::Center),
                 )]
@@ -272,18 +775,60 @@ impl<State> NodeValue<'_, State> {
                 width: available_area.width,
                 height: available_area.height,
             }],
-            NodeValue::Visibility { .. } => {
+            NodeValue::Scroll {
+                axis,
+                offset,
+                element,
+                ..
+            } => {
+                // Contents are measured without a bound along the scroll axis
+                // & laid out at their natural size, contents without one fill the scroll container
+                let constraints = element
+                    .constraints(available_area.unbounded(*axis), state, ctx)
+                    .unwrap_or_default();
+                let natural = |constraint: Constraint, available: f32| {
+                    Some(constraint.clamping(f32::INFINITY))
+                        .filter(|size| size.is_finite())
+                        .unwrap_or(constraint.clamping(available))
+                };
+                vec![match axis {
+                    Axis::Horizontal => Area {
+                        x: available_area.x - *offset,
+                        width: natural(constraints.width, available_area.width),
+                        ..available_area
+                    },
+                    Axis::Vertical => Area {
+                        y: available_area.y - *offset,
+                        height: natural(constraints.height, available_area.height),
+                        ..available_area
+                    },
+                }]
+            }
+            NodeValue::Visibility { .. }
+            | NodeValue::GridItem { .. }
+            | NodeValue::Id { .. }
+            | NodeValue::Direction { .. }
+            | NodeValue::Cached { .. }
+            | NodeValue::Pointer { .. }
+            | NodeValue::Focusable { .. }
+            | NodeValue::FocusScope { .. }
+            | NodeValue::Semantics { .. }
+            | NodeValue::Animate { .. }
+            | NodeValue::MatchedGeometry { .. } => {
                 vec![available_area]
             }
             NodeValue::Draw(_)
+            | NodeValue::VirtualList { .. }
             | NodeValue::Space
             | NodeValue::AreaReader { .. }
             | NodeValue::Coupled { .. }
             | NodeValue::NodeTrait { .. }
-            | NodeValue::Dynamic { .. } => {
+            | NodeValue::Dynamic { .. }
+            | NodeValue::Stateful { .. }
+            | NodeValue::Group(_)
+            | NodeValue::Empty => {
                 vec![available_area]
             }
-            NodeValue::Group(_) | NodeValue::Empty => unreachable!(),
         }
     }
 
@@ -293,15 +838,43 @@ impl<State> NodeValue<'_, State> {
         contextual_x_align: Option<XAlign>,
         contextual_y_align: Option<YAlign>,
         state: &mut State,
+        ctx: &mut LayoutCtx,
     ) {
+        if let Some(dump) = &mut ctx.dump {
+            dump.enter(self, available_area);
+        }
+        // Scroll containers are expected to have content larger than themselves
+        let bounds = match self {
+            NodeValue::Scroll { .. } => None,
+            _ => Some(available_area),
+        };
+        // Matches the order children are drawn in by `NodeValue::draw`
+        let reversed = matches!(
+            self,
+            NodeValue::Column { .. }
+                | NodeValue::Row { .. }
+                | NodeValue::Flow { .. }
+                | NodeValue::VirtualList { .. }
+                | NodeValue::Coupled { over: false, .. }
+        );
+        ctx.enter_node(self.kind_name(), available_area, bounds, reversed);
         let contextual_aligns = self.contextual_aligns();
 
-        let allocated = self.allocate_area(
+        let mut allocated = self.allocate_area(
             available_area,
             contextual_aligns.0.or(contextual_x_align),
             contextual_aligns.1.or(contextual_y_align),
             state,
+            ctx,
         );
+        // Areas are always allocated left to right,
+        // mirroring each allocation within it's container mirrors the entire subtree.
+        // Offsets are absolute, so they aren't mirrored.
+        if ctx.direction == Direction::RightToLeft && !matches!(self, NodeValue::Offset { .. }) {
+            allocated
+                .iter_mut()
+                .for_each(|area| *area = area.mirrored(available_area));
+        }
 
         match self {
             NodeValue::Column {
@@ -315,41 +888,79 @@ impl<State> NodeValue<'_, State> {
                 align: x_align,
                 off_axis_align: y_align,
                 ..
+            }
+            | NodeValue::Flow {
+                elements,
+                align: x_align,
+                off_axis_align: y_align,
+                ..
             } => {
                 elements
                     .iter_mut()
                     .zip(allocated)
-                    .for_each(|(el, allocation)| el.layout(allocation, *x_align, *y_align, state));
+                    .for_each(|(el, allocation)| {
+                        el.layout(allocation, *x_align, *y_align, state, ctx)
+                    });
             }
-            NodeValue::Stack { elements, .. } => {
+            NodeValue::Stack { elements, .. }
+            | NodeValue::Grid { elements, .. }
+            | NodeValue::Custom { elements, .. } => {
                 elements
                     .iter_mut()
                     .zip(allocated)
-                    .for_each(|(el, allocation)| el.layout(allocation, None, None, state));
+                    .for_each(|(el, allocation)| el.layout(allocation, None, None, state, ctx));
             }
             NodeValue::Padding { element, .. }
-            | NodeValue::Explicit { element, .. }
-            | NodeValue::Offset { element, .. } => {
-                element.layout(allocated[0], None, None, state);
+            | NodeValue::Offset { element, .. }
+            | NodeValue::GridItem { element, .. } => {
+                element.layout(allocated[0], None, None, state, ctx);
+            }
+            NodeValue::Explicit { options, element } => {
+                let bounds = [
+                    (Axis::Horizontal, options.width_min, options.width_max),
+                    (Axis::Vertical, options.height_min, options.height_max),
+                ];
+                for (axis, min, max) in bounds {
+                    if let (Some(min), Some(max)) = (min, max) {
+                        if min > max {
+                            ctx.diagnose(DiagnosticKind::MinGreaterThanMax { axis, min, max });
+                        }
+                    }
+                }
+                element.layout(allocated[0], None, None, state, ctx);
             }
             NodeValue::Draw(drawable) => {
                 drawable.area = allocated[0];
                 drawable.area.width = drawable.area.width.max(0.);
                 drawable.area.height = drawable.area.height.max(0.);
+                drawable.clipped = ctx
+                    .viewport
+                    .is_some_and(|viewport| !drawable.area.intersects(viewport));
+                drawable.focused = ctx.in_focus;
+                drawable.progress = ctx.transition;
+                drawable.opacity = ctx.opacity;
             }
             NodeValue::Space => (),
-            NodeValue::AreaReader { read } => {
-                *self = read(allocated[0], state).inner;
-                self.layout(allocated[0], None, None, state);
+            NodeValue::AreaReader { read, computed } => {
+                let node = match computed.take() {
+                    Some(node) if ctx.reusing => node,
+                    _ => Box::new(NodeCache::new(read(allocated[0], state).inner)),
+                };
+                computed
+                    .insert(node)
+                    .layout(allocated[0], None, None, state, ctx);
             }
             NodeValue::Coupled {
                 element, coupled, ..
             } => {
-                element.layout(allocated[0], None, None, state);
-                coupled.layout(allocated[0], None, None, state);
+                element.layout(allocated[0], None, None, state, ctx);
+                coupled.layout(allocated[0], None, None, state, ctx);
             }
-            NodeValue::Visibility { element, .. } => {
-                element.layout(allocated[0], None, None, state);
+            NodeValue::Visibility { element, visible } => {
+                let outer_visible = ctx.visible;
+                ctx.visible = outer_visible && *visible;
+                element.layout(allocated[0], None, None, state, ctx);
+                ctx.visible = outer_visible;
             }
             NodeValue::NodeTrait { node } => {
                 node.layout(
@@ -357,19 +968,260 @@ impl<State> NodeValue<'_, State> {
                     contextual_x_align,
                     contextual_y_align,
                     state,
+                    ctx,
                 );
             }
             NodeValue::Dynamic { node, computed } => {
-                let mut node = NodeCache::new(node(state).inner);
-                node.layout(
+                // The node built when this pass measured it is laid out,
+                // as are the nodes of unchanged cached subtrees
+                let node = match computed.take() {
+                    Some(node) if node.is_current(ctx) => node,
+                    _ => Box::new(NodeCache::new(node(state).inner)),
+                };
+                computed.insert(node).layout(
+                    available_area,
+                    contextual_x_align,
+                    contextual_y_align,
+                    state,
+                    ctx,
+                );
+            }
+            NodeValue::Stateful { node, computed } => {
+                let node = match computed.take() {
+                    Some(node) if node.is_current(ctx) => node,
+                    _ => Box::new(NodeCache::new(node(&mut ctx.store, state).inner)),
+                };
+                computed.insert(node).layout(
                     available_area,
                     contextual_x_align,
                     contextual_y_align,
                     state,
+                    ctx,
+                );
+            }
+            NodeValue::Scroll {
+                axis,
+                element,
+                on_content_size,
+                ..
+            } => {
+                if let Some(on_content_size) = on_content_size {
+                    match axis {
+                        Axis::Horizontal => {
+                            on_content_size(allocated[0].width, available_area.width, state)
+                        }
+                        Axis::Vertical => {
+                            on_content_size(allocated[0].height, available_area.height, state)
+                        }
+                    }
+                }
+                let outer_viewport = ctx.viewport;
+                ctx.viewport = Some(
+                    outer_viewport
+                        .map_or(available_area, |outer| outer.intersection(available_area)),
+                );
+                element.layout(allocated[0], None, None, state, ctx);
+                ctx.viewport = outer_viewport;
+            }
+            NodeValue::VirtualList {
+                count,
+                spacing,
+                extents,
+                builder,
+                align,
+                off_axis_align,
+                computed,
+            } => {
+                let x_align = off_axis_align.unwrap_or(XAlign::Center);
+                let y_align = align.unwrap_or(YAlign::Center);
+                extents.prepare(*count, *spacing, state, ctx.pass);
+                let pool = available_area.height - extents.length(*count, *spacing);
+                let list_start = match y_align {
+                    YAlign::Top => available_area.y,
+                    YAlign::Center => available_area.y + (pool * 0.5),
+                    YAlign::Bottom => available_area.y + pool,
+                };
+                let visible = ctx.viewport.map_or(available_area, |viewport| {
+                    viewport.intersection(available_area)
+                });
+                // Only rows which intersect the visible region are built
+                computed.clear();
+                let first = extents.first_after(visible.y - list_start, *count, *spacing);
+                for i in first..*count {
+                    let (start, extent) = extents.item(i, *spacing);
+                    let current_pos = list_start + start;
+                    if current_pos >= visible.y + visible.height {
+                        break;
+                    }
+                    if current_pos + extent > visible.y {
+                        let mut node = NodeCache::new(builder(i, state).inner);
+                        let area = Area {
+                            x: available_area.x,
+                            y: current_pos,
+                            width: available_area.width,
+                            height: extent,
+                        };
+                        let constraints = node.constraints(area, state, ctx).unwrap_or_default();
+                        let mut area = area.constrained(&constraints, x_align, y_align);
+                        if ctx.direction == Direction::RightToLeft {
+                            area = area.mirrored(available_area);
+                        }
+                        node.layout(area, Some(x_align), Some(y_align), state, ctx);
+                        computed.push(node);
+                    }
+                }
+            }
+            NodeValue::Direction { direction, element } => {
+                let outer_direction = ctx.direction;
+                ctx.direction = *direction;
+                element.layout(
+                    allocated[0],
+                    contextual_x_align,
+                    contextual_y_align,
+                    state,
+                    ctx,
+                );
+                ctx.direction = outer_direction;
+            }
+            NodeValue::Cached {
+                key,
+                element,
+                laid_out,
+            } => {
+                let key = key.get(state);
+                let outer_reusing = ctx.reusing;
+                ctx.reusing = outer_reusing || *laid_out == Some((key, allocated[0]));
+                element.layout(
+                    allocated[0],
+                    contextual_x_align,
+                    contextual_y_align,
+                    state,
+                    ctx,
+                );
+                ctx.reusing = outer_reusing;
+                *laid_out = Some((key, allocated[0]));
+            }
+            NodeValue::Id { id, element, .. } => {
+                ctx.areas.insert(*id, allocated[0]);
+                ctx.record_hit_area(*id, allocated[0]);
+                element.layout(
+                    allocated[0],
+                    contextual_x_align,
+                    contextual_y_align,
+                    state,
+                    ctx,
                 );
-                *computed = Some(Box::new(node))
             }
-            NodeValue::Group(_) | NodeValue::Empty => unreachable!(),
+            NodeValue::Pointer {
+                element, target, ..
+            } => {
+                *target = Some((ctx.enter_pointer_target(allocated[0]), allocated[0]));
+                element.layout(
+                    allocated[0],
+                    contextual_x_align,
+                    contextual_y_align,
+                    state,
+                    ctx,
+                );
+                ctx.exit_pointer_target();
+            }
+            NodeValue::Focusable {
+                id,
+                tab_index,
+                neighbors,
+                element,
+            } => {
+                // Hidden nodes can't be focused
+                if ctx.visible {
+                    ctx.focus
+                        .record_focusable(*id, *tab_index, allocated[0], neighbors.clone());
+                }
+                let outer_focus = ctx.in_focus;
+                ctx.in_focus = outer_focus || ctx.focused == Some(*id);
+                element.layout(
+                    allocated[0],
+                    contextual_x_align,
+                    contextual_y_align,
+                    state,
+                    ctx,
+                );
+                ctx.in_focus = outer_focus;
+            }
+            NodeValue::FocusScope { element } => {
+                ctx.focus.enter_scope();
+                element.layout(
+                    allocated[0],
+                    contextual_x_align,
+                    contextual_y_align,
+                    state,
+                    ctx,
+                );
+                ctx.focus.exit_scope();
+            }
+            NodeValue::Animate {
+                key,
+                curve,
+                duration,
+                element,
+            } => {
+                let (area, progress) =
+                    ctx.store
+                        .animate(*key, allocated[0], ctx.time, *curve, *duration);
+                ctx.animating |= progress < 1.;
+                let outer_transition = ctx.transition;
+                ctx.transition = progress;
+                element.layout(area, contextual_x_align, contextual_y_align, state, ctx);
+                ctx.transition = outer_transition;
+            }
+            NodeValue::MatchedGeometry {
+                key,
+                curve,
+                duration,
+                element,
+            } => {
+                let (area, progress, opacity) =
+                    ctx.store
+                        .match_geometry(*key, allocated[0], ctx.time, *curve, *duration);
+                ctx.animating |= progress < 1. || opacity < 1.;
+                let (outer_transition, outer_opacity) = (ctx.transition, ctx.opacity);
+                ctx.transition = progress;
+                ctx.opacity *= opacity;
+                element.layout(area, contextual_x_align, contextual_y_align, state, ctx);
+                (ctx.transition, ctx.opacity) = (outer_transition, outer_opacity);
+            }
+            NodeValue::Semantics {
+                role,
+                label,
+                value,
+                element,
+            } => {
+                // Hidden nodes aren't exposed to assistive technology
+                let recorded = ctx.visible;
+                if recorded {
+                    ctx.enter_semantics(AccessibilityNode {
+                        role: *role,
+                        label: label.clone(),
+                        value: value.clone(),
+                        bounds: allocated[0],
+                        children: Vec::new(),
+                    });
+                }
+                element.layout(
+                    allocated[0],
+                    contextual_x_align,
+                    contextual_y_align,
+                    state,
+                    ctx,
+                );
+                if recorded {
+                    ctx.exit_semantics();
+                }
+            }
+            NodeValue::Group(_) | NodeValue::Empty => ctx.misplaced_node(),
+        }
+        ctx.exit_node();
+        if let Some(dump) = &mut ctx.dump {
+            dump.exit();
         }
     }
 }
@@ -445,12 +1297,12 @@ pub(crate) fn layout_axis<State>(
     x_align: XAlign,
     y_align: YAlign,
     state: &mut State,
-    check: bool,
+    ctx: &mut LayoutCtx,
 ) -> Vec<Area> {
     let element_count = elements.len();
     let sizes: Vec<Option<SizeConstraints>> = elements
         .iter_mut()
-        .map(|element| element.constraints(available_area, state))
+        .map(|element| element.constraints(available_area, state, ctx))
         .collect();
     let filtered_element_count = sizes.iter().filter_map(|&el| el).count();
 
@@ -460,12 +1312,18 @@ pub(crate) fn layout_axis<State>(
         Orientation::Vertical => available_area.height,
     } - total_spacing;
 
-    let default_size = available_size / filtered_element_count as f32;
+    let grow: Vec<f32> = sizes
+        .iter()
+        .map(|size| size.map_or(0., |size| size.grow.unwrap_or(1.)))
+        .collect();
+    let shrink: Vec<f32> = sizes
+        .iter()
+        .map(|size| size.map_or(0., |size| size.shrink.unwrap_or(1.)))
+        .collect();
+    let total_grow: f32 = grow.iter().sum();
 
-    let mut pool = 0.;
     let mut final_sizes = vec![None; element_count];
-    let mut room_to_grow = vec![0.0; element_count];
-    let mut room_to_shrink = vec![0.0; element_count];
+    let mut bounds = vec![(0., f32::INFINITY); element_count];
 
     for (i, size_constraint) in sizes.iter().enumerate() {
         if let Some(size_constraint) = size_constraint {
@@ -473,7 +1331,12 @@ pub(crate) fn layout_axis<State>(
                 Orientation::Horizontal => size_constraint.width,
                 Orientation::Vertical => size_constraint.height,
             };
-            let mut final_size = Option::<f32>::None;
+            // Space is split between elements according to their grow factors
+            let default_size = if total_grow > 0. {
+                available_size * grow[i] / total_grow
+            } else {
+                0.
+            };
             let mut lower = constraint.get_lower();
             let mut upper = constraint.get_upper();
 
@@ -492,103 +1355,35 @@ pub(crate) fn layout_axis<State>(
                 }
             }
 
-            if let Some(lower) = lower {
-                if default_size < lower {
-                    pool += default_size - lower;
-                    final_size = Some(lower);
-                }
-            }
-            if let Some(upper) = upper {
-                if default_size > upper {
-                    pool += default_size - upper;
-                    final_size = Some(upper);
-                }
-            }
-
-            if let Some(lower) = lower {
-                if default_size >= lower {
-                    room_to_shrink[i] = -(final_size.unwrap_or(default_size) - lower);
-                }
-            } else {
-                // Effectively, this means the element can shrink to 0
-                room_to_shrink[i] = -default_size;
-            }
-
-            if let Some(upper) = upper {
-                if default_size <= upper {
-                    room_to_grow[i] = -(final_size.unwrap_or(default_size) - upper);
-                }
-            } else {
-                // Effectively, this means the element can expand any amount
-                room_to_grow[i] = default_size * 10.;
-            }
-
-            final_sizes[i] = final_size.unwrap_or(default_size).into();
+            let lower = lower.unwrap_or(0.);
+            let upper = upper.unwrap_or(f32::INFINITY).max(lower);
+            bounds[i] = (lower, upper);
+            final_sizes[i] = Some(default_size.clamp(lower, upper));
         }
     }
 
-    fn can_accommodate(room: &[f32]) -> bool {
-        room.iter().filter(|r| r.abs() > 0.).count() as f32 > 0.
-    }
-
-    let limit = 5;
-    let mut i = 0;
-    loop {
-        if i > limit {
-            break;
-        }
-        i += 1;
-        let pool_empty = pool.abs() < 0.1;
-        if !pool_empty && pool.is_sign_positive() && can_accommodate(&room_to_grow) {
-            // We need to use more room
-            let mut enumerated_room: Vec<(usize, f32)> = room_to_grow
-                .iter()
-                .enumerate()
-                .map(|(i, v)| (i, *v))
-                .filter(|(_, v)| *v != 0.)
-                .collect();
-            enumerated_room.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
-            let distribution_candidates = room_to_grow
-                .iter()
-                .filter(|r| r.abs() > 0. && r.is_sign_positive())
-                .count() as f32;
-            let distribution_amount =
-                (pool / distribution_candidates).min(enumerated_room.first().unwrap().1);
-            pool -= distribution_amount * distribution_candidates;
-            enumerated_room.iter().for_each(|&(i, _)| {
-                if room_to_grow[i].abs() > 0. && room_to_grow[i].is_sign_positive() {
-                    room_to_grow[i] -= distribution_amount;
-                    if let Some(size) = &mut final_sizes[i] {
-                        *size += distribution_amount
-                    }
-                }
-            });
-        } else if !pool_empty && pool.is_sign_negative() && can_accommodate(&room_to_shrink) {
-            // We need to use less room
-            let mut enumerated_room: Vec<(usize, f32)> = room_to_shrink
-                .iter()
-                .enumerate()
-                .map(|(i, v)| (i, *v))
-                .filter(|(_, v)| *v != 0.)
-                .collect();
-            enumerated_room.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap().reverse());
-            let distribution_candidates = room_to_shrink
-                .iter()
-                .filter(|r| r.abs() > 0. && r.is_sign_negative())
-                .count() as f32;
-            let distribution_amount =
-                (pool / distribution_candidates).max(enumerated_room.first().unwrap().1);
-            pool -= distribution_amount * distribution_candidates;
-            enumerated_room.iter().for_each(|&(i, _)| {
-                if room_to_shrink[i].abs() > 0. && room_to_shrink[i].is_sign_negative() {
-                    room_to_shrink[i] -= distribution_amount;
-                    if let Some(size) = &mut final_sizes[i] {
-                        *size += distribution_amount
-                    }
-                }
-            });
-        } else {
-            break;
+    // Whatever space is left after clamping is shared between elements with room to grow (or shrink)
+    // in proportion to their grow (or shrink) factors, solved exactly in a single pass.
+    let mut pool = available_size - final_sizes.iter().flatten().sum::<f32>();
+    let growing = pool > 0.;
+    let room: Vec<(Constraint, f32)> = final_sizes
+        .iter()
+        .zip(&bounds)
+        .enumerate()
+        .map(|(i, (size, &(lower, upper)))| match size {
+            Some(size) if growing => (
+                Constraint::new(Some(0.), Some(upper - size).filter(|room| room.is_finite())),
+                grow[i],
+            ),
+            Some(size) => (Constraint::new(Some(0.), Some(size - lower)), shrink[i]),
+            None => (Constraint::new(Some(0.), Some(0.)), 0.),
+        })
+        .collect();
+    let direction = if growing { 1. } else { -1. };
+    for (size, adjustment) in final_sizes.iter_mut().zip(distribute(pool.abs(), &room)) {
+        if let Some(size) = size {
+            *size += adjustment * direction;
+            pool -= adjustment * direction;
         }
     }
 
@@ -606,7 +1401,7 @@ pub(crate) fn layout_axis<State>(
     };
 
     let mut areas = Vec::<Area>::new();
-    for (i, child) in elements.iter_mut().enumerate() {
+    for i in 0..element_count {
         let child_size = final_sizes[i].unwrap_or(if filtered_element_count > 1 {
             0.
         } else {
@@ -616,7 +1411,7 @@ pub(crate) fn layout_axis<State>(
             }
         });
 
-        let area = match orientation {
+        let slice = match orientation {
             Orientation::Horizontal => Area {
                 x: current_pos,
                 y: available_area.y,
@@ -629,18 +1424,125 @@ pub(crate) fn layout_axis<State>(
                 width: available_area.width,
                 height: child_size,
             },
+        };
+        // The off-axis size of elements such as wrapping text depends on their size along the axis,
+        // so they're measured again using their final size
+        let constraints = match sizes[i] {
+            Some(size) if !elements[i].area_dependent => size,
+            Some(size) => match elements[i].constraints(slice, state, ctx) {
+                Some(fitted) => match orientation {
+                    Orientation::Horizontal => SizeConstraints {
+                        height: fitted.height,
+                        ..size
+                    },
+                    Orientation::Vertical => SizeConstraints {
+                        width: fitted.width,
+                        ..size
+                    },
+                },
+                None => size,
+            },
+            None => SizeConstraints::default(),
+        };
+        let area = slice.constrained(&constraints, x_align, y_align);
+        areas.push(area);
+
+        if sizes[i].is_some() {
+            current_pos += child_size + *spacing;
         }
-        .constrained(&sizes[i].unwrap_or_default(), x_align, y_align);
+    }
+    areas
+}
 
-        if !check {
-            child.layout(area, Some(x_align), Some(y_align), state);
-        } else {
-            areas.push(area);
+pub(crate) struct FlowLine {
+    pub(crate) items: Vec<(usize, f32)>,
+    pub(crate) width: f32,
+    pub(crate) height: f32,
+}
+
+/// Greedily breaks elements into lines which fit within the available width
+pub(crate) fn flow_lines(
+    sizes: &[Option<SizeConstraints>],
+    spacing: f32,
+    available_area: Area,
+) -> Vec<FlowLine> {
+    let mut lines = Vec::<FlowLine>::new();
+    for (i, size) in sizes.iter().enumerate() {
+        let Some(size) = size else {
+            continue;
+        };
+        let natural = available_area.constrained(size, XAlign::Leading, YAlign::Top);
+        match lines.last_mut() {
+            Some(line) if line.width + spacing + natural.width <= available_area.width => {
+                line.items.push((i, natural.width));
+                line.width += spacing + natural.width;
+                line.height = line.height.max(natural.height);
+            }
+            _ => lines.push(FlowLine {
+                items: vec![(i, natural.width)],
+                width: natural.width,
+                height: natural.height,
+            }),
         }
+    }
+    lines
+}
 
-        if sizes[i].is_some() {
-            current_pos += child_size + *spacing;
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn layout_flow<State>(
+    elements: &mut [NodeCache<'_, State>],
+    spacing: f32,
+    line_spacing: f32,
+    available_area: Area,
+    x_align: XAlign,
+    y_align: YAlign,
+    state: &mut State,
+    ctx: &mut LayoutCtx,
+) -> Vec<Area> {
+    let sizes: Vec<Option<SizeConstraints>> = elements
+        .iter_mut()
+        .map(|element| element.constraints(available_area, state, ctx))
+        .collect();
+    let lines = flow_lines(&sizes, spacing, available_area);
+
+    let total_line_spacing = line_spacing * (lines.len() as i32 - 1).max(0) as f32;
+    let pool = available_area.height
+        - lines.iter().map(|line| line.height).sum::<f32>()
+        - total_line_spacing;
+    let mut current_y = match y_align {
+        YAlign::Top => available_area.y,
+        YAlign::Center => available_area.y + (pool * 0.5),
+        YAlign::Bottom => available_area.y + pool,
+    };
+
+    // Hidden elements are still allocated an empty area
+    let mut areas = vec![
+        Area {
+            x: available_area.x,
+            y: available_area.y,
+            width: 0.,
+            height: 0.,
+        };
+        elements.len()
+    ];
+    for line in lines {
+        let pool = available_area.width - line.width;
+        let mut current_x = match x_align {
+            XAlign::Leading => available_area.x,
+            XAlign::Center => available_area.x + (pool * 0.5),
+            XAlign::Trailing => available_area.x + pool,
+        };
+        for (i, width) in line.items {
+            areas[i] = Area {
+                x: current_x,
+                y: current_y,
+                width,
+                height: line.height,
+            }
+            .constrained(&sizes[i].unwrap_or_default(), x_align, y_align);
+            current_x += width + spacing;
         }
+        current_y += line.height + line_spacing;
     }
     areas
 }
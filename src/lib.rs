@@ -13,19 +13,30 @@ See [`Layout`] for setup.
 See [`Node`] for layout customization.
 */
 
+mod cache;
+pub use cache::LayoutCache;
 mod constraints;
+mod context;
 mod debug;
 mod drawable;
+mod dump;
+mod events;
+mod focus;
+mod grid;
 mod layout;
 pub use layout::Layout;
+mod lifecycle;
 mod modifiers;
 mod node;
 pub use node::Node;
 mod node_cache;
 mod scoper;
 pub use scoper::{ScopeCtx, ScopeCtxResult};
+mod store;
+pub use store::ComponentStore;
 mod subtree;
 mod tests;
+mod virtual_list;
 
 /// Traits for layout definitions
 pub mod traits;
@@ -35,3 +46,52 @@ pub mod models;
 
 /// Layout core node construction
 pub mod nodes;
+
+/// Layout descriptions loaded at runtime
+#[cfg(feature = "loader")]
+pub mod loader;
+
+// Lets `layout!`, which refers to `::backer`, be used in the crate's own tests
+#[cfg(all(test, feature = "macros"))]
+extern crate self as backer;
+
+/**
+Builds a node tree from nested containers & modifiers.
+
+Containers are written as `name(arguments) { children }` & expand to the functions in [`nodes`]:
+- `column`, `row`, `stack`, `flow` & `group`
+- `column(spacing: 10)`, `row(spacing: 10)` & `flow(spacing: 10, line_spacing: 5)`
+- `grid(columns: ..., rows: ...)`, with optional `column_gap` & `row_gap`
+- `scroll(axis: ..., offset: ...)` containing exactly one node
+
+Modifiers follow a container's braces, like `.pad(5)`.
+Any other expression which returns a [`Node`] can be used as a child, & children may be
+separated by commas. Integer literals can be used wherever a modifier or container takes an `f32`.
+
+`if`, `if let` & `for` can be used within a list of children.
+
+```rust
+use backer::{layout, models::*, nodes::*, Layout};
+
+let show_footer = true;
+let mut drawn = vec![];
+Layout::new(layout! {
+    column(spacing: 10) {
+        row {
+            for index in 0..3 {
+                draw(move |area, drawn: &mut Vec<(usize, Area)>| drawn.push((index, area)))
+            }
+        }
+        .height(20)
+        if show_footer {
+            draw(|area, drawn: &mut Vec<(usize, Area)>| drawn.push((3, area)))
+        }
+    }
+    .pad(5)
+})
+.draw(Area::new(0., 0., 100., 100.), &mut drawn);
+assert!(drawn.contains(&(3, Area::new(5., 35., 90., 60.))));
+```
+ */
+#[cfg(feature = "macros")]
+pub use backer_macros::layout;
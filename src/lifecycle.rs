@@ -0,0 +1,109 @@
+use crate::{context::LayoutCtx, layout::NodeValue, models::NodeId};
+use std::rc::Rc;
+
+pub(crate) type LifecycleFn<'nodes, State> = Rc<dyn Fn(&mut State) + 'nodes>;
+
+/// `Node::on_disappear` callbacks, retained until the pass after their nodes were laid out
+pub(crate) type DisappearFns<State> = Vec<(NodeId, LifecycleFn<'static, State>)>;
+
+/// Callbacks for a node with an id, see `Node::on_appear` & `Node::on_disappear`
+pub(crate) struct Lifecycle<'nodes, State> {
+    pub(crate) on_appear: Option<LifecycleFn<'nodes, State>>,
+    /// Retained by the layout's `ComponentStore` after the node is gone, so it can't borrow from the tree
+    pub(crate) on_disappear: Option<LifecycleFn<'static, State>>,
+}
+
+impl<State> Default for Lifecycle<'_, State> {
+    fn default() -> Self {
+        Self {
+            on_appear: None,
+            on_disappear: None,
+        }
+    }
+}
+
+/// Calls the callbacks in `previous` whose ids weren't found in the most recent pass
+pub(crate) fn fire_disappeared<State>(
+    previous: DisappearFns<State>,
+    state: &mut State,
+    ctx: &LayoutCtx,
+) {
+    for (id, on_disappear) in previous {
+        if !ctx.store.found(id) {
+            on_disappear(state);
+        }
+    }
+}
+
+impl<State> NodeValue<'_, State> {
+    /// Calls the `on_appear` callbacks of nodes in this subtree whose ids appeared in the most recent pass,
+    /// & collects the `on_disappear` callbacks of every node in the subtree.
+    ///
+    /// Callbacks within `scope` use scoped state, so they're called by the scope & retained by the store.
+    pub(crate) fn fire_lifecycle(
+        &mut self,
+        state: &mut State,
+        ctx: &mut LayoutCtx,
+        on_disappear: &mut DisappearFns<State>,
+    ) {
+        match self {
+            NodeValue::Id {
+                id,
+                lifecycle,
+                element,
+            } => {
+                if let Some(on_appear) = &lifecycle.on_appear {
+                    if ctx.store.appeared(*id) {
+                        on_appear(state);
+                    }
+                }
+                if let Some(handler) = &lifecycle.on_disappear {
+                    on_disappear.push((*id, handler.clone()));
+                }
+                element.kind.fire_lifecycle(state, ctx, on_disappear)
+            }
+            NodeValue::Padding { element, .. }
+            | NodeValue::Explicit { element, .. }
+            | NodeValue::Offset { element, .. }
+            | NodeValue::GridItem { element, .. }
+            | NodeValue::Scroll { element, .. }
+            | NodeValue::Direction { element, .. }
+            | NodeValue::Cached { element, .. }
+            | NodeValue::Visibility { element, .. }
+            | NodeValue::Pointer { element, .. }
+            | NodeValue::Focusable { element, .. }
+            | NodeValue::FocusScope { element }
+            | NodeValue::Semantics { element, .. }
+            | NodeValue::Animate { element, .. }
+            | NodeValue::MatchedGeometry { element, .. } => {
+                element.kind.fire_lifecycle(state, ctx, on_disappear)
+            }
+            NodeValue::Column { elements, .. }
+            | NodeValue::Row { elements, .. }
+            | NodeValue::Stack { elements, .. }
+            | NodeValue::Flow { elements, .. }
+            | NodeValue::Grid { elements, .. }
+            | NodeValue::Custom { elements, .. }
+            | NodeValue::VirtualList {
+                computed: elements, ..
+            } => elements
+                .iter_mut()
+                .for_each(|element| element.kind.fire_lifecycle(state, ctx, on_disappear)),
+            NodeValue::Coupled {
+                element, coupled, ..
+            } => {
+                element.kind.fire_lifecycle(state, ctx, on_disappear);
+                coupled.kind.fire_lifecycle(state, ctx, on_disappear);
+            }
+            NodeValue::Dynamic { computed, .. }
+            | NodeValue::Stateful { computed, .. }
+            | NodeValue::AreaReader { computed, .. } => {
+                if let Some(computed) = computed {
+                    computed.kind.fire_lifecycle(state, ctx, on_disappear)
+                }
+            }
+            NodeValue::NodeTrait { node } => node.fire_lifecycle(state, ctx),
+            NodeValue::Draw(_) | NodeValue::Space | NodeValue::Group(_) | NodeValue::Empty => (),
+        }
+    }
+}
@@ -0,0 +1,974 @@
+/*!
+Builds layouts from a text description at runtime, so that structure & spacing can be changed without recompiling.
+
+Drawing is bound to named slots using a [`Registry`](crate::loader::Registry).
+
+```rust
+use backer::loader::{load, Registry};
+use backer::models::*;
+use backer::Layout;
+
+let description = r#"
+    // Comments start with `//`
+    column_spaced(10) {
+        slot("title").height(20)
+        row {
+            slot("avatar").width(40).aspect(1)
+            space
+        }
+    }
+    .pad(10)
+"#;
+let registry = Registry::new()
+    .draw("title", |area, drawn: &mut Vec<Area>| drawn.push(area))
+    .draw("avatar", |area, drawn: &mut Vec<Area>| drawn.push(area));
+let mut drawn = vec![];
+Layout::new(load(description, &registry).unwrap()).draw(Area::new(0., 0., 100., 100.), &mut drawn);
+assert_eq!(drawn, vec![Area::new(15., 45., 40., 40.), Area::new(10., 10., 80., 20.)]);
+```
+
+# Format
+
+A description contains a single node. Each node is a name, followed by arguments in parentheses,
+children in braces & modifiers, each of which are optional:
+```text
+name(argument, argument) { child child }.modifier(argument).modifier
+```
+
+Nodes mirror the functions in `nodes`:
+- `column`, `row`, `stack`, `flow` & `group` contain any number of children
+- `column_spaced(spacing)`, `row_spaced(spacing)` & `flow_spaced(spacing, line_spacing)`
+- `grid(columns, rows)` & `grid_spaced(column_gap, row_gap, columns, rows)`, where tracks are lists such as
+  `[fixed(100), fraction(1), range(10, none), fit]`
+- `scroll(axis, offset)` contains exactly one child
+- `space` & `empty`
+- `slot("name")`, which is replaced with the node registered under `name`
+
+Modifiers mirror the methods on `Node`: `pad`, `pad_x`, `pad_y`, `pad_top`, `pad_bottom`, `pad_leading`, `pad_trailing`,
+`offset`, `offset_x`, `offset_y`, `width`, `height`, `width_range`, `height_range`, `align`, `align_contents`,
+`aspect`, `expand`, `expand_x`, `expand_y`, `grow`, `shrink`, `visible`, `grid_cell`, `grid_span`, `direction`,
+`cache_key`, `id`, `focusable`, `tab_index`, `focus_neighbor`, `focus_scope`, `semantics` & `semantic_value`.
+`tab_index` & `focus_neighbor` must directly follow `focusable` (or each other) & `semantic_value` must directly
+follow `semantics`, see [`Node::focusable_with`] & [`Node::semantics_with_value`].
+`attach_over` & `attach_under` take a single child in braces, such as `.attach_over { slot("badge") }`.
+Nodes & modifiers which take closures are provided by slots.
+
+Arguments are numbers, `"strings"`, ranges such as `10..100`, `10..` & `..100`, lists in brackets,
+or names written in snake case, such as `top_leading`, `vertical`, `right_to_left`, `text_input` or `true`.
+ */
+
+use crate::{
+    drawable::SomeDrawable,
+    layout::NodeValue,
+    models::*,
+    nodes::{self, draw_object},
+    traits::Drawable,
+    Node,
+};
+use std::{collections::HashMap, fmt, rc::Rc};
+
+/// An error found while loading a layout description, see [`load`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadError {
+    /// The line the error occurred on, starting at 1
+    pub line: usize,
+    /// The column the error occurred at, starting at 1
+    pub column: usize,
+    /// A description of the error
+    pub message: String,
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+type SlotFn<'nodes, State> = Box<dyn Fn() -> Node<'nodes, State> + 'nodes>;
+
+/// The nodes which slots in a layout description are replaced with, see [`load`]
+pub struct Registry<'nodes, State> {
+    slots: HashMap<String, SlotFn<'nodes, State>>,
+}
+
+impl<State> Default for Registry<'_, State> {
+    fn default() -> Self {
+        Self {
+            slots: HashMap::new(),
+        }
+    }
+}
+
+impl<State> fmt::Debug for Registry<'_, State> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Registry")
+            .field("slots", &self.slots.keys())
+            .finish()
+    }
+}
+
+impl<'nodes, State> Registry<'nodes, State> {
+    /// Creates an empty [`Registry`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Registers a node built by `build` for each slot named `name`
+    pub fn node(
+        mut self,
+        name: impl Into<String>,
+        build: impl Fn() -> Node<'nodes, State> + 'nodes,
+    ) -> Self {
+        self.slots.insert(name.into(), Box::new(build));
+        self
+    }
+    /// Registers a node drawn like `nodes::draw` for each slot named `name`
+    pub fn draw(
+        self,
+        name: impl Into<String>,
+        drawable_fn: impl Fn(Area, &mut State) + 'nodes,
+    ) -> Self {
+        let drawable_fn = Rc::new(drawable_fn);
+        self.node(name, move || {
+            let drawable_fn = drawable_fn.clone();
+            nodes::drawable_node(SomeDrawable::Fn(Box::new(move |area, state| {
+                drawable_fn(area, state)
+            })))
+        })
+    }
+    /// Registers a `nodes::draw_object` node for each slot named `name`, using a drawable built by `build`
+    pub fn draw_object<D: Drawable<State> + 'nodes>(
+        self,
+        name: impl Into<String>,
+        build: impl Fn() -> D + 'nodes,
+    ) -> Self {
+        self.node(name, move || draw_object(build()))
+    }
+}
+
+/// Parses a layout description & builds it using the slots in `registry`
+///
+/// See the [module documentation](self) for the format.
+pub fn load<'nodes, State>(
+    description: &str,
+    registry: &Registry<'nodes, State>,
+) -> Result<Node<'nodes, State>, LoadError> {
+    Description::parse(description)?.build(registry)
+}
+
+/// A parsed layout description, which can be built many times, see [`load`]
+#[derive(Debug, Clone)]
+pub struct Description {
+    root: Element,
+}
+
+impl Description {
+    /// Parses a layout description, reporting any errors which don't depend on the registry.
+    pub fn parse(description: &str) -> Result<Self, LoadError> {
+        let mut parser = Parser {
+            tokens: tokenize(description)?,
+            position: 0,
+        };
+        let root = parser.element()?;
+        let next = parser.peek();
+        if next.kind != TokenKind::End {
+            return Err(next.error(format!(
+                "expected the end of the description, found {}",
+                next.kind
+            )));
+        }
+        // Building checks node names & arguments
+        build_element::<()>(&root, &|_| Ok(nodes::space()))?;
+        Ok(Self { root })
+    }
+    /// Builds the description, replacing each slot with the node registered in `registry`.
+    pub fn build<'nodes, State>(
+        &self,
+        registry: &Registry<'nodes, State>,
+    ) -> Result<Node<'nodes, State>, LoadError> {
+        build_element(&self.root, &|value: &Value| {
+            let name = value.string()?;
+            registry
+                .slots
+                .get(name)
+                .map(|build| build())
+                .ok_or_else(|| value.error(format!("no slot named {name:?} is registered")))
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Position {
+    line: usize,
+    column: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Name(String),
+    Number(f32),
+    String(String),
+    OpenParen,
+    CloseParen,
+    OpenBrace,
+    CloseBrace,
+    OpenBracket,
+    CloseBracket,
+    Comma,
+    Dot,
+    Range,
+    End,
+}
+
+impl fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenKind::Name(name) => write!(f, "`{name}`"),
+            TokenKind::Number(number) => write!(f, "`{number}`"),
+            TokenKind::String(string) => write!(f, "{string:?}"),
+            TokenKind::OpenParen => write!(f, "`(`"),
+            TokenKind::CloseParen => write!(f, "`)`"),
+            TokenKind::OpenBrace => write!(f, "`{{`"),
+            TokenKind::CloseBrace => write!(f, "`}}`"),
+            TokenKind::OpenBracket => write!(f, "`[`"),
+            TokenKind::CloseBracket => write!(f, "`]`"),
+            TokenKind::Comma => write!(f, "`,`"),
+            TokenKind::Dot => write!(f, "`.`"),
+            TokenKind::Range => write!(f, "`..`"),
+            TokenKind::End => write!(f, "the end of the description"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    position: Position,
+}
+
+impl Token {
+    fn error(&self, message: String) -> LoadError {
+        error_at(self.position, message)
+    }
+}
+
+fn error_at(position: Position, message: String) -> LoadError {
+    LoadError {
+        line: position.line,
+        column: position.column,
+        message,
+    }
+}
+
+struct Lexer {
+    chars: Vec<char>,
+    index: usize,
+    position: Position,
+}
+
+impl Lexer {
+    fn peek(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.index + offset).copied()
+    }
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek(0)?;
+        self.index += 1;
+        if c == '\n' {
+            self.position.line += 1;
+            self.position.column = 1;
+        } else {
+            self.position.column += 1;
+        }
+        Some(c)
+    }
+}
+
+fn tokenize(description: &str) -> Result<Vec<Token>, LoadError> {
+    let mut tokens = Vec::new();
+    let mut lexer = Lexer {
+        chars: description.chars().collect(),
+        index: 0,
+        position: Position { line: 1, column: 1 },
+    };
+    while let Some(c) = lexer.peek(0) {
+        let start = lexer.position;
+        let kind =
+            match c {
+                c if c.is_whitespace() => {
+                    lexer.next();
+                    continue;
+                }
+                '/' => {
+                    lexer.next();
+                    if lexer.next() != Some('/') {
+                        return Err(error_at(
+                            start,
+                            "expected `//` to start a comment".to_string(),
+                        ));
+                    }
+                    while !matches!(lexer.next(), Some('\n') | None) {}
+                    continue;
+                }
+                '(' | ')' | '{' | '}' | '[' | ']' | ',' => {
+                    lexer.next();
+                    match c {
+                        '(' => TokenKind::OpenParen,
+                        ')' => TokenKind::CloseParen,
+                        '{' => TokenKind::OpenBrace,
+                        '}' => TokenKind::CloseBrace,
+                        '[' => TokenKind::OpenBracket,
+                        ']' => TokenKind::CloseBracket,
+                        _ => TokenKind::Comma,
+                    }
+                }
+                '.' => {
+                    lexer.next();
+                    if lexer.peek(0) == Some('.') {
+                        lexer.next();
+                        TokenKind::Range
+                    } else {
+                        TokenKind::Dot
+                    }
+                }
+                '"' => {
+                    lexer.next();
+                    let mut string = String::new();
+                    loop {
+                        match lexer.next() {
+                            Some('"') => break,
+                            Some('\\') => match lexer.next() {
+                                Some('n') => string.push('\n'),
+                                Some(c @ ('"' | '\\')) => string.push(c),
+                                _ => return Err(error_at(
+                                    start,
+                                    "invalid escape in string, expected `\\\"`, `\\\\` or `\\n`"
+                                        .to_string(),
+                                )),
+                            },
+                            Some(c) => string.push(c),
+                            None => return Err(error_at(start, "unterminated string".to_string())),
+                        }
+                    }
+                    TokenKind::String(string)
+                }
+                c if c.is_ascii_digit() || c == '-' => {
+                    let mut number = String::new();
+                    number.extend(lexer.next());
+                    loop {
+                        match lexer.peek(0) {
+                            Some(c) if c.is_ascii_digit() => number.extend(lexer.next()),
+                            // A `.` which isn't followed by a digit may start a range
+                            Some('.')
+                                if !number.contains('.')
+                                    && lexer.peek(1).is_some_and(|c| c.is_ascii_digit()) =>
+                            {
+                                number.extend(lexer.next())
+                            }
+                            _ => break,
+                        }
+                    }
+                    TokenKind::Number(
+                        number
+                            .parse()
+                            .map_err(|_| error_at(start, format!("invalid number `{number}`")))?,
+                    )
+                }
+                c if c.is_alphabetic() || c == '_' => {
+                    let mut name = String::new();
+                    while lexer
+                        .peek(0)
+                        .is_some_and(|c| c.is_alphanumeric() || c == '_')
+                    {
+                        name.extend(lexer.next());
+                    }
+                    TokenKind::Name(name)
+                }
+                c => return Err(error_at(start, format!("unexpected character `{c}`"))),
+            };
+        tokens.push(Token {
+            kind,
+            position: start,
+        });
+    }
+    tokens.push(Token {
+        kind: TokenKind::End,
+        position: lexer.position,
+    });
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone)]
+enum ValueKind {
+    Number(f32),
+    String(String),
+    Name(String),
+    Call(String, Vec<Value>),
+    List(Vec<Value>),
+    Range(Option<f32>, Option<f32>),
+}
+
+#[derive(Debug, Clone)]
+struct Value {
+    kind: ValueKind,
+    position: Position,
+}
+
+#[derive(Debug, Clone)]
+struct Call {
+    name: String,
+    arguments: Vec<Value>,
+    children: Option<Vec<Element>>,
+    position: Position,
+}
+
+#[derive(Debug, Clone)]
+struct Element {
+    node: Call,
+    modifiers: Vec<Call>,
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.position.min(self.tokens.len() - 1)]
+    }
+    fn next(&mut self) -> Token {
+        let token = self.peek().clone();
+        self.position += 1;
+        token
+    }
+    fn eat(&mut self, kind: TokenKind) -> bool {
+        if self.peek().kind == kind {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+    fn expect(&mut self, kind: TokenKind) -> Result<Token, LoadError> {
+        let token = self.next();
+        if token.kind == kind {
+            Ok(token)
+        } else {
+            Err(token.error(format!("expected {kind}, found {}", token.kind)))
+        }
+    }
+    fn name(&mut self, expected: &str) -> Result<(String, Position), LoadError> {
+        let token = self.next();
+        match token.kind.clone() {
+            TokenKind::Name(name) => Ok((name, token.position)),
+            kind => Err(token.error(format!("expected {expected}, found {kind}"))),
+        }
+    }
+    fn element(&mut self) -> Result<Element, LoadError> {
+        let node = self.call("a node")?;
+        let mut modifiers = Vec::new();
+        while self.eat(TokenKind::Dot) {
+            modifiers.push(self.call("a modifier")?);
+        }
+        Ok(Element { node, modifiers })
+    }
+    fn call(&mut self, expected: &str) -> Result<Call, LoadError> {
+        let (name, position) = self.name(expected)?;
+        let arguments = if self.eat(TokenKind::OpenParen) {
+            self.values(TokenKind::CloseParen)?
+        } else {
+            Vec::new()
+        };
+        let children = if self.eat(TokenKind::OpenBrace) {
+            let mut children = Vec::new();
+            while !self.eat(TokenKind::CloseBrace) {
+                children.push(self.element()?);
+                self.eat(TokenKind::Comma);
+            }
+            Some(children)
+        } else {
+            None
+        };
+        Ok(Call {
+            name,
+            arguments,
+            children,
+            position,
+        })
+    }
+    /// Parses comma separated values up to & including `close`
+    fn values(&mut self, close: TokenKind) -> Result<Vec<Value>, LoadError> {
+        let mut values = Vec::new();
+        while !self.eat(close.clone()) {
+            values.push(self.value()?);
+            if !self.eat(TokenKind::Comma) {
+                self.expect(close)?;
+                break;
+            }
+        }
+        Ok(values)
+    }
+    fn value(&mut self) -> Result<Value, LoadError> {
+        let token = self.next();
+        let kind = match token.kind.clone() {
+            TokenKind::Number(start) => {
+                if self.eat(TokenKind::Range) {
+                    let end = match self.peek().kind {
+                        TokenKind::Number(end) => {
+                            self.next();
+                            Some(end)
+                        }
+                        _ => None,
+                    };
+                    ValueKind::Range(Some(start), end)
+                } else {
+                    ValueKind::Number(start)
+                }
+            }
+            TokenKind::Range => {
+                let end = self.next();
+                match end.kind.clone() {
+                    TokenKind::Number(end) => ValueKind::Range(None, Some(end)),
+                    kind => return Err(end.error(format!("expected a number, found {kind}"))),
+                }
+            }
+            TokenKind::String(string) => ValueKind::String(string),
+            TokenKind::Name(name) => {
+                if self.eat(TokenKind::OpenParen) {
+                    ValueKind::Call(name, self.values(TokenKind::CloseParen)?)
+                } else {
+                    ValueKind::Name(name)
+                }
+            }
+            TokenKind::OpenBracket => ValueKind::List(self.values(TokenKind::CloseBracket)?),
+            kind => return Err(token.error(format!("expected a value, found {kind}"))),
+        };
+        Ok(Value {
+            kind,
+            position: token.position,
+        })
+    }
+}
+
+impl Value {
+    fn error(&self, message: String) -> LoadError {
+        error_at(self.position, message)
+    }
+    fn expected<T>(&self, expected: &str) -> Result<T, LoadError> {
+        let found = match &self.kind {
+            ValueKind::Number(number) => format!("`{number}`"),
+            ValueKind::String(string) => format!("{string:?}"),
+            ValueKind::Name(name) | ValueKind::Call(name, _) => format!("`{name}`"),
+            ValueKind::List(_) => "a list".to_string(),
+            ValueKind::Range(..) => "a range".to_string(),
+        };
+        Err(self.error(format!("expected {expected}, found {found}")))
+    }
+    fn number(&self) -> Result<f32, LoadError> {
+        match self.kind {
+            ValueKind::Number(number) => Ok(number),
+            _ => self.expected("a number"),
+        }
+    }
+    /// The value as a whole number, if it fits in `T`
+    fn count<T: TryFrom<u64>>(&self) -> Result<T, LoadError> {
+        match self.kind {
+            ValueKind::Number(number) if number >= 0. && number.fract() == 0. => {
+                // Every whole `f32` below 2^64 converts to `u64` exactly
+                let count = (number < u64::MAX as f32).then_some(number as u64);
+                match count.and_then(|count| T::try_from(count).ok()) {
+                    Some(count) => Ok(count),
+                    None => self.expected("a smaller whole number"),
+                }
+            }
+            _ => self.expected("a whole number"),
+        }
+    }
+    fn string(&self) -> Result<&str, LoadError> {
+        match &self.kind {
+            ValueKind::String(string) => Ok(string),
+            _ => self.expected("a string"),
+        }
+    }
+    fn range(&self) -> Result<(Option<f32>, Option<f32>), LoadError> {
+        match self.kind {
+            ValueKind::Range(start, end) => Ok((start, end)),
+            ValueKind::Number(number) => Ok((Some(number), Some(number))),
+            _ => self.expected("a range"),
+        }
+    }
+    /// Matches a name against `options`, described as `expected` in errors
+    fn one_of<T: Copy>(&self, expected: &str, options: &[(&str, T)]) -> Result<T, LoadError> {
+        match &self.kind {
+            ValueKind::Name(name) => match options.iter().find(|(option, _)| option == name) {
+                Some((_, value)) => Ok(*value),
+                None => Err(self.error(format!(
+                    "unknown {expected} `{name}`, expected one of {}",
+                    options
+                        .iter()
+                        .map(|(option, _)| format!("`{option}`"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ))),
+            },
+            _ => self.expected(expected),
+        }
+    }
+    fn boolean(&self) -> Result<bool, LoadError> {
+        self.one_of("boolean", &[("true", true), ("false", false)])
+    }
+    fn align(&self) -> Result<Align, LoadError> {
+        self.one_of(
+            "alignment",
+            &[
+                ("top", Align::Top),
+                ("center_y", Align::CenterY),
+                ("bottom", Align::Bottom),
+                ("leading", Align::Leading),
+                ("center_x", Align::CenterX),
+                ("trailing", Align::Trailing),
+                ("top_leading", Align::TopLeading),
+                ("top_center", Align::TopCenter),
+                ("top_trailing", Align::TopTrailing),
+                ("center_trailing", Align::CenterTrailing),
+                ("bottom_trailing", Align::BottomTrailing),
+                ("bottom_center", Align::BottomCenter),
+                ("bottom_leading", Align::BottomLeading),
+                ("center_leading", Align::CenterLeading),
+                ("center_center", Align::CenterCenter),
+            ],
+        )
+    }
+    fn axis(&self) -> Result<Axis, LoadError> {
+        self.one_of(
+            "axis",
+            &[
+                ("horizontal", Axis::Horizontal),
+                ("vertical", Axis::Vertical),
+            ],
+        )
+    }
+    fn direction(&self) -> Result<Direction, LoadError> {
+        self.one_of(
+            "direction",
+            &[
+                ("left_to_right", Direction::LeftToRight),
+                ("right_to_left", Direction::RightToLeft),
+            ],
+        )
+    }
+    fn navigation_direction(&self) -> Result<NavigationDirection, LoadError> {
+        self.one_of(
+            "navigation direction",
+            &[
+                ("left", NavigationDirection::Left),
+                ("right", NavigationDirection::Right),
+                ("up", NavigationDirection::Up),
+                ("down", NavigationDirection::Down),
+            ],
+        )
+    }
+    fn role(&self) -> Result<Role, LoadError> {
+        self.one_of(
+            "role",
+            &[
+                ("group", Role::Group),
+                ("dialog", Role::Dialog),
+                ("button", Role::Button),
+                ("check_box", Role::CheckBox),
+                ("link", Role::Link),
+                ("text", Role::Text),
+                ("heading", Role::Heading),
+                ("image", Role::Image),
+                ("text_input", Role::TextInput),
+                ("slider", Role::Slider),
+                ("list", Role::List),
+                ("list_item", Role::ListItem),
+            ],
+        )
+    }
+    fn tracks(&self) -> Result<Vec<Track>, LoadError> {
+        let ValueKind::List(values) = &self.kind else {
+            return self.expected("a list of tracks");
+        };
+        values
+            .iter()
+            .map(|value| match &value.kind {
+                ValueKind::Name(name) if name == "fit" => Ok(Track::Fit),
+                ValueKind::Call(name, arguments) => {
+                    let optional = |value: &Value| match &value.kind {
+                        ValueKind::Name(name) if name == "none" => Ok(None),
+                        _ => value.number().map(Some),
+                    };
+                    match (name.as_str(), arguments.as_slice()) {
+                        ("fixed", [size]) => Ok(Track::Fixed(size.number()?)),
+                        ("fraction", [weight]) => Ok(Track::Fraction(weight.number()?)),
+                        ("range", [min, max]) => Ok(Track::Range {
+                            min: optional(min)?,
+                            max: optional(max)?,
+                        }),
+                        _ => value.expected(
+                            "`fixed(size)`, `fraction(weight)`, `range(min, max)` or `fit`",
+                        ),
+                    }
+                }
+                _ => {
+                    value.expected("`fixed(size)`, `fraction(weight)`, `range(min, max)` or `fit`")
+                }
+            })
+            .collect()
+    }
+}
+
+impl Call {
+    fn error(&self, message: String) -> LoadError {
+        error_at(self.position, message)
+    }
+    /// The arguments, if there are exactly `N` of them
+    fn arguments<const N: usize>(&self) -> Result<&[Value; N], LoadError> {
+        self.arguments.as_slice().try_into().map_err(|_| {
+            self.error(format!(
+                "`{}` takes {N} argument{}, found {}",
+                self.name,
+                if N == 1 { "" } else { "s" },
+                self.arguments.len()
+            ))
+        })
+    }
+    fn no_children(&self) -> Result<(), LoadError> {
+        match self.children {
+            Some(_) => Err(self.error(format!("`{}` can't contain children", self.name))),
+            None => Ok(()),
+        }
+    }
+    fn children<'nodes, State>(
+        &self,
+        slot: &dyn Fn(&Value) -> Result<Node<'nodes, State>, LoadError>,
+    ) -> Result<Vec<Node<'nodes, State>>, LoadError> {
+        self.children
+            .iter()
+            .flatten()
+            .map(|child| build_element(child, slot))
+            .collect()
+    }
+    fn only_child<'nodes, State>(
+        &self,
+        slot: &dyn Fn(&Value) -> Result<Node<'nodes, State>, LoadError>,
+    ) -> Result<Node<'nodes, State>, LoadError> {
+        let mut children = self.children(slot)?;
+        if children.len() != 1 {
+            return Err(self.error(format!(
+                "`{}` must contain exactly one child, found {}",
+                self.name,
+                children.len()
+            )));
+        }
+        Ok(children.remove(0))
+    }
+}
+
+fn build_element<'nodes, State>(
+    element: &Element,
+    slot: &dyn Fn(&Value) -> Result<Node<'nodes, State>, LoadError>,
+) -> Result<Node<'nodes, State>, LoadError> {
+    let call = &element.node;
+    let mut node = match call.name.as_str() {
+        "column" | "row" | "stack" | "flow" | "group" => {
+            call.arguments::<0>()?;
+            let children = call.children(slot)?;
+            match call.name.as_str() {
+                "column" => nodes::column(children),
+                "row" => nodes::row(children),
+                "stack" => nodes::stack(children),
+                "flow" => nodes::flow(children),
+                _ => nodes::group(children),
+            }
+        }
+        "column_spaced" => {
+            let [spacing] = call.arguments()?;
+            nodes::column_spaced(spacing.number()?, call.children(slot)?)
+        }
+        "row_spaced" => {
+            let [spacing] = call.arguments()?;
+            nodes::row_spaced(spacing.number()?, call.children(slot)?)
+        }
+        "flow_spaced" => {
+            let [spacing, line_spacing] = call.arguments()?;
+            nodes::flow_spaced(
+                spacing.number()?,
+                line_spacing.number()?,
+                call.children(slot)?,
+            )
+        }
+        "grid" => {
+            let [columns, rows] = call.arguments()?;
+            nodes::grid(columns.tracks()?, rows.tracks()?, call.children(slot)?)
+        }
+        "grid_spaced" => {
+            let [column_gap, row_gap, columns, rows] = call.arguments()?;
+            nodes::grid_spaced(
+                column_gap.number()?,
+                row_gap.number()?,
+                columns.tracks()?,
+                rows.tracks()?,
+                call.children(slot)?,
+            )
+        }
+        "scroll" => {
+            let [axis, offset] = call.arguments()?;
+            nodes::scroll(axis.axis()?, offset.number()?, call.only_child(slot)?)
+        }
+        "space" | "empty" => {
+            call.arguments::<0>()?;
+            call.no_children()?;
+            match call.name.as_str() {
+                "space" => nodes::space(),
+                _ => nodes::empty(),
+            }
+        }
+        "slot" => {
+            let [name] = call.arguments()?;
+            call.no_children()?;
+            slot(name)?
+        }
+        name => return Err(call.error(format!("unknown node `{name}`"))),
+    };
+    for modifier in &element.modifiers {
+        node = apply_modifier(node, modifier, slot)?;
+    }
+    Ok(node)
+}
+
+fn apply_modifier<'nodes, State>(
+    mut node: Node<'nodes, State>,
+    modifier: &Call,
+    slot: &dyn Fn(&Value) -> Result<Node<'nodes, State>, LoadError>,
+) -> Result<Node<'nodes, State>, LoadError> {
+    if let "attach_over" | "attach_under" = modifier.name.as_str() {
+        modifier.arguments::<0>()?;
+        let attached = modifier.only_child(slot)?;
+        return Ok(match modifier.name.as_str() {
+            "attach_over" => node.attach_over(attached),
+            _ => node.attach_under(attached),
+        });
+    }
+    modifier.no_children()?;
+    let number = |node: Node<'nodes, State>,
+                  f: fn(Node<'nodes, State>, f32) -> Node<'nodes, State>| {
+        let [amount] = modifier.arguments()?;
+        Ok::<_, LoadError>(f(node, amount.number()?))
+    };
+    let pair = |node: Node<'nodes, State>,
+                f: fn(Node<'nodes, State>, usize, usize) -> Node<'nodes, State>| {
+        let [first, second] = modifier.arguments()?;
+        Ok::<_, LoadError>(f(node, first.count()?, second.count()?))
+    };
+    Ok(match modifier.name.as_str() {
+        "pad" => number(node, Node::pad)?,
+        "pad_x" => number(node, Node::pad_x)?,
+        "pad_y" => number(node, Node::pad_y)?,
+        "pad_top" => number(node, Node::pad_top)?,
+        "pad_bottom" => number(node, Node::pad_bottom)?,
+        "pad_leading" => number(node, Node::pad_leading)?,
+        "pad_trailing" => number(node, Node::pad_trailing)?,
+        "offset_x" => number(node, Node::offset_x)?,
+        "offset_y" => number(node, Node::offset_y)?,
+        "width" => number(node, Node::width)?,
+        "height" => number(node, Node::height)?,
+        "aspect" => number(node, Node::aspect)?,
+        "grow" => number(node, Node::grow)?,
+        "shrink" => number(node, Node::shrink)?,
+        "grid_cell" => pair(node, Node::grid_cell)?,
+        "grid_span" => pair(node, Node::grid_span)?,
+        "offset" => {
+            let [x, y] = modifier.arguments()?;
+            node.offset(x.number()?, y.number()?)
+        }
+        "width_range" | "height_range" => {
+            let [range] = modifier.arguments()?;
+            let (min, max) = range.range()?;
+            let bounds = (
+                min.map_or(std::ops::Bound::Unbounded, std::ops::Bound::Included),
+                max.map_or(std::ops::Bound::Unbounded, std::ops::Bound::Included),
+            );
+            match modifier.name.as_str() {
+                "width_range" => node.width_range(bounds),
+                _ => node.height_range(bounds),
+            }
+        }
+        "align" => {
+            let [align] = modifier.arguments()?;
+            node.align(align.align()?)
+        }
+        "align_contents" => {
+            let [align] = modifier.arguments()?;
+            node.align_contents(align.align()?)
+        }
+        "expand" | "expand_x" | "expand_y" | "focus_scope" => {
+            modifier.arguments::<0>()?;
+            match modifier.name.as_str() {
+                "expand" => node.expand(),
+                "expand_x" => node.expand_x(),
+                "expand_y" => node.expand_y(),
+                _ => node.focus_scope(),
+            }
+        }
+        "visible" => {
+            let [visible] = modifier.arguments()?;
+            node.visible(visible.boolean()?)
+        }
+        "direction" => {
+            let [direction] = modifier.arguments()?;
+            node.direction(direction.direction()?)
+        }
+        "cache_key" => {
+            let [key] = modifier.arguments()?;
+            node.cache_key(key.count()?)
+        }
+        "id" => {
+            let [id] = modifier.arguments()?;
+            node.id(id.string()?)
+        }
+        "focusable" => {
+            let [id] = modifier.arguments()?;
+            node.focusable(id.string()?)
+        }
+        "tab_index" | "focus_neighbor" => {
+            let NodeValue::Focusable {
+                ref mut tab_index,
+                ref mut neighbors,
+                ..
+            } = node.inner
+            else {
+                return Err(modifier.error(format!(
+                    "`{}` must directly follow `focusable`",
+                    modifier.name
+                )));
+            };
+            if modifier.name == "tab_index" {
+                let [index] = modifier.arguments()?;
+                *tab_index = Some(index.count()?);
+            } else {
+                let [direction, id] = modifier.arguments()?;
+                neighbors.push((direction.navigation_direction()?, NodeId::new(id.string()?)));
+            }
+            node
+        }
+        "semantics" => {
+            let [role, label] = modifier.arguments()?;
+            node.semantics(role.role()?, label.string()?)
+        }
+        "semantic_value" => {
+            let NodeValue::Semantics { ref mut value, .. } = node.inner else {
+                return Err(
+                    modifier.error("`semantic_value` must directly follow `semantics`".to_string())
+                );
+            };
+            let [current] = modifier.arguments()?;
+            *value = Some(current.string()?.to_string());
+            node
+        }
+        name => return Err(modifier.error(format!("unknown modifier `{name}`"))),
+    })
+}
@@ -1,4 +1,8 @@
-use std::rc::Rc;
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    rc::Rc,
+};
 
 /// An alignment along the X and/or Y axis
 #[derive(Debug, Clone, Copy)]
@@ -106,6 +110,540 @@ impl Area {
             height: 0.,
         }
     }
+    /// Mirrors the area horizontally within `container`
+    pub(crate) fn mirrored(self, container: Area) -> Area {
+        Area {
+            x: container.x + container.width - (self.x - container.x) - self.width,
+            ..self
+        }
+    }
+    pub(crate) fn intersects(&self, other: Area) -> bool {
+        self.x < other.x + other.width
+            && other.x < self.x + self.width
+            && self.y < other.y + other.height
+            && other.y < self.y + self.height
+    }
+    pub(crate) fn contains(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height
+    }
+    /// The area with an unbounded extent along `axis`, for measuring the contents of a scroll container
+    pub(crate) fn unbounded(self, axis: Axis) -> Area {
+        match axis {
+            Axis::Horizontal => Area {
+                width: f32::INFINITY,
+                ..self
+            },
+            Axis::Vertical => Area {
+                height: f32::INFINITY,
+                ..self
+            },
+        }
+    }
+    /// The area `amount` of the way from `self` to `other`
+    pub(crate) fn lerp(self, other: Area, amount: f32) -> Area {
+        let lerp = |from: f32, to: f32| from + (to - from) * amount;
+        Area {
+            x: lerp(self.x, other.x),
+            y: lerp(self.y, other.y),
+            width: lerp(self.width, other.width),
+            height: lerp(self.height, other.height),
+        }
+    }
+    pub(crate) fn intersection(&self, other: Area) -> Area {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        Area {
+            x,
+            y,
+            width: ((self.x + self.width).min(other.x + other.width) - x).max(0.),
+            height: ((self.y + self.height).min(other.y + other.height) - y).max(0.),
+        }
+    }
+}
+
+/// An axis along which content can be laid out or scrolled
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Axis {
+    /// The x axis
+    Horizontal,
+    /// The y axis
+    Vertical,
+}
+
+/// The range of sizes a node can take along one axis
+///
+/// See [`Constraints`]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Bounds {
+    /// The minimum size, `None` if there is no minimum
+    pub min: Option<f32>,
+    /// The maximum size, `None` if there is no maximum
+    pub max: Option<f32>,
+}
+
+impl Bounds {
+    /// Bounds which allow any size
+    pub fn none() -> Self {
+        Self::default()
+    }
+    /// Bounds which only allow `size`
+    pub fn fixed(size: f32) -> Self {
+        Self {
+            min: Some(size),
+            max: Some(size),
+        }
+    }
+    /// Bounds which allow any size larger than `min`
+    pub fn at_least(min: f32) -> Self {
+        Self {
+            min: Some(min),
+            max: None,
+        }
+    }
+    /// Bounds which allow any size smaller than `max`
+    pub fn at_most(max: f32) -> Self {
+        Self {
+            min: None,
+            max: Some(max),
+        }
+    }
+}
+
+/// The sizes a node can take, used to measure the children of a `traits::LayoutAlgorithm`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Constraints {
+    /// The range of widths
+    pub width: Bounds,
+    /// The range of heights
+    pub height: Bounds,
+}
+
+/// The space offered to a node when measuring it, see `Node::measure`
+///
+/// `None` means the space is unbounded along that axis.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ProposedSize {
+    /// The proposed width
+    pub width: Option<f32>,
+    /// The proposed height
+    pub height: Option<f32>,
+}
+
+/// The size a node needs in response to a [`ProposedSize`], see `Node::measure`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct MeasuredSize {
+    /// The measured width
+    pub width: f32,
+    /// The measured height
+    pub height: f32,
+}
+
+/// An error which prevents a layout from being drawn, see `Layout::try_draw`
+#[derive(Debug, Clone, PartialEq)]
+pub enum LayoutError {
+    /// `nodes::empty` or `nodes::group` was laid out on it's own rather than as the child of a container,
+    /// for example at the root of the layout, wrapped in a modifier, or returned from `nodes::dynamic`
+    MisplacedNode {
+        /// The path to the node, see [`Diagnostic::path`]
+        path: Vec<String>,
+    },
+}
+
+impl std::fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LayoutError::MisplacedNode { path } => {
+                write!(f, "{} must be placed in a container", path.join(" > "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for LayoutError {}
+
+/// A recoverable problem found during layout, see `Layout::diagnostics`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /// The path from the root to the node, each segment is the kind of node
+    /// followed by it's index within it's parent, such as `["Column", "Row[1]", "Explicit[0]"]`
+    pub path: Vec<String>,
+    /// The problem & how it was resolved
+    pub kind: DiagnosticKind,
+}
+
+/// A recoverable problem found during layout & the policy used to resolve it
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiagnosticKind {
+    /// A size modifier's minimum is larger than it's maximum.
+    ///
+    /// The minimum wins - the maximum is raised to the minimum.
+    MinGreaterThanMax {
+        /// The axis of the conflicting constraints
+        axis: Axis,
+        /// The minimum size
+        min: f32,
+        /// The maximum size
+        max: f32,
+    },
+    /// A size modifier, or a size returned by a closure such as `dynamic_height` or `measure`, is NaN.
+    ///
+    /// The size is ignored, as if it wasn't set. Sizes measured as NaN are treated as zero.
+    NotANumber {
+        /// The modifier which produced the size, such as `"width"` or `"measure"`
+        source: &'static str,
+    },
+    /// A node's minimum size is larger than the area available to it.
+    ///
+    /// The node keeps it's minimum size & overflows the available area,
+    /// positioned according to it's alignment.
+    Overflow {
+        /// The axis which overflows
+        axis: Axis,
+        /// The size of the node
+        size: f32,
+        /// The size of the available area
+        available: f32,
+    },
+}
+
+/// The horizontal direction of a layout
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Direction {
+    /// Leading is on the left, trailing is on the right - the default direction
+    #[default]
+    LeftToRight,
+    /// Leading is on the right, trailing is on the left.
+    ///
+    /// Rows are laid out starting from the right,
+    /// leading & trailing alignment & padding are mirrored.
+    RightToLeft,
+}
+
+/// A direction to move focus in, see `Layout::navigate`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NavigationDirection {
+    /// Towards the left edge of the layout
+    Left,
+    /// Towards the right edge of the layout
+    Right,
+    /// Towards the top edge of the layout
+    Up,
+    /// Towards the bottom edge of the layout
+    Down,
+}
+
+/// How a focusable node takes part in focus navigation, see `Node::focusable_with`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FocusOptions {
+    /// Places the node before nodes without a tab index in tab order,
+    /// nodes with lower tab indices come first.
+    pub tab_index: Option<u32>,
+    /// Overrides the node which `Layout::navigate` moves focus to from this node in each direction.
+    ///
+    /// Geometry is used as usual if the neighbor can't be focused.
+    pub neighbors: Vec<(NavigationDirection, NodeId)>,
+}
+
+/// The kind of element a node represents to assistive technology, see `Node::semantics`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// A container which groups related elements
+    Group,
+    /// A window, dialog or other top-level surface
+    Dialog,
+    /// A button which performs an action when activated
+    Button,
+    /// A check box or toggle
+    CheckBox,
+    /// A link to other content
+    Link,
+    /// Static text
+    Text,
+    /// A heading for a section of content
+    Heading,
+    /// An image
+    Image,
+    /// An editable text field
+    TextInput,
+    /// A control for choosing a value from a range
+    Slider,
+    /// A list of items
+    List,
+    /// An item in a list
+    ListItem,
+}
+
+/// A node in the tree returned by `Layout::accessibility_tree`
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccessibilityNode {
+    /// The kind of element
+    pub role: Role,
+    /// The name announced for the element
+    pub label: String,
+    /// The current value of the element, such as the contents of a text field
+    pub value: Option<String>,
+    /// The area the node was laid out in
+    pub bounds: Area,
+    /// The nodes with semantics inside this node, in tree order
+    pub children: Vec<AccessibilityNode>,
+}
+
+/// A stable identifier for a node, see [`crate::Node::id`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId(u64);
+
+impl NodeId {
+    /// Creates a [`NodeId`] by hashing `id`.
+    ///
+    /// The same value always produces the same [`NodeId`].
+    /// Integers are hashed by value regardless of their type, so `1_i32` & `1_usize` produce the same [`NodeId`],
+    /// while `1` & `"1"` don't.
+    pub fn new(id: impl Hash) -> Self {
+        let mut hasher = IdHasher(DefaultHasher::new());
+        id.hash(&mut hasher);
+        Self(hasher.finish())
+    }
+}
+
+/// Widens every integer before hashing it, see [`NodeId::new`]
+struct IdHasher(DefaultHasher);
+
+macro_rules! widen {
+    ($($method:ident: $int:ty),*) => {
+        $(fn $method(&mut self, i: $int) {
+            self.write_i128(i as i128)
+        })*
+    };
+}
+
+impl Hasher for IdHasher {
+    fn finish(&self) -> u64 {
+        self.0.finish()
+    }
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.write(bytes)
+    }
+    fn write_i128(&mut self, i: i128) {
+        self.0.write_u8(0);
+        self.0.write_i128(i)
+    }
+    fn write_u128(&mut self, i: u128) {
+        // Values which fit in an `i128` are hashed the same as the signed integers
+        match i128::try_from(i) {
+            Ok(i) => self.write_i128(i),
+            Err(_) => {
+                self.0.write_u8(1);
+                self.0.write_u128(i)
+            }
+        }
+    }
+    widen!(write_u8: u8, write_u16: u16, write_u32: u32, write_u64: u64, write_usize: usize);
+    widen!(write_i8: i8, write_i16: i16, write_i32: i32, write_i64: i64, write_isize: isize);
+}
+
+/// A framework-agnostic pointer event, see `Layout::dispatch`
+///
+/// Positions are in the same coordinate space as the area passed to `Layout::layout`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PointerEvent {
+    /// A button was pressed
+    Down {
+        /// The x position of the pointer
+        x: f32,
+        /// The y position of the pointer
+        y: f32,
+        /// The button which was pressed
+        button: PointerButton,
+    },
+    /// A button was released
+    Up {
+        /// The x position of the pointer
+        x: f32,
+        /// The y position of the pointer
+        y: f32,
+        /// The button which was released
+        button: PointerButton,
+    },
+    /// The pointer moved
+    Move {
+        /// The x position of the pointer
+        x: f32,
+        /// The y position of the pointer
+        y: f32,
+    },
+    /// A scroll wheel or trackpad scrolled
+    Scroll {
+        /// The x position of the pointer
+        x: f32,
+        /// The y position of the pointer
+        y: f32,
+        /// The horizontal scroll distance
+        delta_x: f32,
+        /// The vertical scroll distance
+        delta_y: f32,
+    },
+    /// The pointer entered a node, or the window when passed to `Layout::dispatch`
+    Enter {
+        /// The x position of the pointer
+        x: f32,
+        /// The y position of the pointer
+        y: f32,
+    },
+    /// The pointer left a node, or the window when passed to `Layout::dispatch`
+    Leave {
+        /// The x position of the pointer
+        x: f32,
+        /// The y position of the pointer
+        y: f32,
+    },
+}
+
+impl PointerEvent {
+    /// The position of the pointer when the event occurred
+    pub fn position(&self) -> (f32, f32) {
+        match *self {
+            PointerEvent::Down { x, y, .. }
+            | PointerEvent::Up { x, y, .. }
+            | PointerEvent::Move { x, y }
+            | PointerEvent::Scroll { x, y, .. }
+            | PointerEvent::Enter { x, y }
+            | PointerEvent::Leave { x, y } => (x, y),
+        }
+    }
+}
+
+/// A pointer button, see [`PointerEvent`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointerButton {
+    /// The primary button, usually the left mouse button or a touch
+    Primary,
+    /// The secondary button, usually the right mouse button
+    Secondary,
+    /// The middle mouse button
+    Middle,
+}
+
+/// Whether a pointer event continues bubbling to enclosing handlers, see `Node::on_pointer`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Propagation {
+    /// The event is delivered to the next enclosing handler
+    Continue,
+    /// The event has been handled & is not delivered to any other handlers
+    Stop,
+}
+
+/// How an animation moves from it's start to it's end, see `Node::animate`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Curve {
+    /// Moves at a constant speed
+    Linear,
+    /// Speeds up from the start & slows down towards the end
+    Ease,
+    /// Moves like a damped spring, which may overshoot before settling at the end
+    Spring {
+        /// How quickly the spring pulls towards the end, in oscillations per duration.
+        ///
+        /// Negative values are treated as `0.`, which doesn't move until the end.
+        stiffness: f32,
+        /// How quickly oscillations die down. `1.` settles as quickly as possible without overshooting,
+        /// & more than `1.` settles more slowly.
+        ///
+        /// Negative values are treated as `0.`, which oscillates without dying down.
+        damping: f32,
+    },
+}
+
+impl Curve {
+    /// The fraction of the distance travelled once `progress` of the duration has elapsed.
+    ///
+    /// Returns `0.` at the start & `1.` at the end, springs may return values outside of this range in between.
+    pub fn value(&self, progress: f32) -> f32 {
+        let progress = progress.clamp(0., 1.);
+        match *self {
+            _ if progress >= 1. => 1.,
+            Curve::Linear => progress,
+            Curve::Ease => progress * progress * (3. - 2. * progress),
+            Curve::Spring { stiffness, damping } => {
+                let (stiffness, damping) = (stiffness.max(0.), damping.max(0.));
+                if stiffness == 0. {
+                    return 0.;
+                }
+                let frequency = stiffness * std::f32::consts::TAU;
+                let decay = damping * frequency;
+                if damping > 1. {
+                    // The sum of a slow & a fast decay, which starts at rest
+                    let spread = frequency * (damping * damping - 1.).sqrt();
+                    let (slow, fast) = (spread - decay, -spread - decay);
+                    1. - (fast * (slow * progress).exp() - slow * (fast * progress).exp())
+                        / (fast - slow)
+                } else if damping == 1. {
+                    1. - (-frequency * progress).exp() * (1. + frequency * progress)
+                } else {
+                    let damped = frequency * (1. - damping * damping).sqrt();
+                    1. - (-decay * progress).exp()
+                        * ((damped * progress).cos() + decay / damped * (damped * progress).sin())
+                }
+            }
+        }
+    }
+}
+
+/// A node with a geometry key which wasn't found in a recent layout pass & is fading out,
+/// see `Node::matched_geometry` & `Layout::departures`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Departure {
+    /// The node's geometry key
+    pub key: NodeId,
+    /// The area the node was last displayed in
+    pub area: Area,
+    /// The opacity to draw the node with, from `1.` down to `0.`
+    pub opacity: f32,
+}
+
+/// The context a drawable is drawn in, see `traits::Drawable::draw_with_ctx` & `nodes::draw_with_ctx`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DrawCtx {
+    /// Whether the node is visible, see `Node::visible`
+    pub visible: bool,
+    /// Whether the node is inside the focused node, see `Node::focusable`
+    pub focused: bool,
+    /// The progress of the innermost enclosing animation, from `0.` when the animation starts to `1.` once it's finished.
+    ///
+    /// Progress increases at a constant rate, regardless of the animation's curve.
+    /// Nodes outside of any animation are drawn with a progress of `1.`, see `Node::animate`
+    pub progress: f32,
+    /// The combined opacity of the enclosing matched geometry nodes, see `Node::matched_geometry`
+    pub opacity: f32,
+}
+
+/// A sizing rule for a row or column track in a grid, see `nodes::grid`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Track {
+    /// A track with an exact size
+    Fixed(f32),
+    /// A track bounded by a minimum and / or maximum size.
+    ///
+    /// Bounds which are `None` are taken from the constraints of the track's contents.
+    Range {
+        /// The minimum size of the track
+        min: Option<f32>,
+        /// The maximum size of the track
+        max: Option<f32>,
+    },
+    /// A track which takes a weighted share of the remaining space,
+    /// while never shrinking below the minimum size of it's contents
+    Fraction(f32),
+    /// A track which is sized to fit it's contents
+    Fit,
+}
+
+/// The position & span of a cell in a grid
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) struct GridPlacement {
+    pub(crate) column: Option<usize>,
+    pub(crate) row: Option<usize>,
+    pub(crate) column_span: Option<usize>,
+    pub(crate) row_span: Option<usize>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -117,6 +655,7 @@ pub(crate) struct Padding {
 }
 
 type DimensionFn<State> = Option<Rc<dyn Fn(f32, &mut State) -> f32>>;
+type MeasureFn<State> = Option<Rc<dyn Fn(ProposedSize, &mut State) -> MeasuredSize>>;
 
 pub(crate) struct Size<State> {
     pub(crate) width_min: Option<f32>,
@@ -128,8 +667,21 @@ pub(crate) struct Size<State> {
     pub(crate) aspect: Option<f32>,
     pub(crate) dynamic_height: DimensionFn<State>,
     pub(crate) dynamic_width: DimensionFn<State>,
+    pub(crate) measure: MeasureFn<State>,
     pub(crate) expand_x: bool,
     pub(crate) expand_y: bool,
+    pub(crate) grow: Option<f32>,
+    pub(crate) shrink: Option<f32>,
+}
+
+impl<State> Size<State> {
+    /// Whether the constraints these options produce depend on the area they're measured in
+    pub(crate) fn depends_on_area(&self) -> bool {
+        self.aspect.is_some()
+            || self.dynamic_height.is_some()
+            || self.dynamic_width.is_some()
+            || self.measure.is_some()
+    }
 }
 
 impl<State> Clone for Size<State> {
@@ -144,8 +696,11 @@ impl<State> Clone for Size<State> {
             aspect: self.aspect,
             dynamic_height: self.dynamic_height.clone(),
             dynamic_width: self.dynamic_width.clone(),
+            measure: self.measure.clone(),
             expand_x: self.expand_x,
             expand_y: self.expand_y,
+            grow: self.grow,
+            shrink: self.shrink,
         }
     }
 }
@@ -162,8 +717,11 @@ impl<State> std::fmt::Debug for Size<State> {
             .field("aspect", &self.aspect)
             .field("dynamic_height", &"<function>")
             .field("dynamic_width", &"<function>")
+            .field("measure", &"<function>")
             .field("expand_x", &self.expand_x)
             .field("expand_y", &self.expand_y)
+            .field("grow", &self.grow)
+            .field("shrink", &self.shrink)
             .finish()
     }
 }
@@ -187,8 +745,11 @@ impl<State> Size<State> {
             aspect: None,
             dynamic_height: None,
             dynamic_width: None,
+            measure: None,
             expand_x: false,
             expand_y: false,
+            grow: None,
+            shrink: None,
         }
     }
 }
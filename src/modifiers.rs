@@ -1,10 +1,14 @@
-use crate::{layout::NodeValue, models::*, node_cache::NodeCache, Node};
-use std::{ops::RangeBounds, rc::Rc};
+use crate::{
+    cache::CacheKey, layout::NodeValue, lifecycle::Lifecycle, models::*, node_cache::NodeCache,
+    Node,
+};
+use std::{hash::Hash, ops::RangeBounds, rc::Rc};
 
 impl<State> Node<'_, State> {
     /// Constrains the node's height as a function of available width.
     ///
     /// Generally you should prefer size constraints, aspect ratio constraints or area readers over dynamic height.
+    /// For text which should wrap inside of a `row`, prefer [`Node::measure`].
     ///
     /// **This is primarily for UI elements such as text** where node height must depend on available width & scaling is
     /// not a simple option.
@@ -26,6 +30,37 @@ impl<State> Node<'_, State> {
             ..Default::default()
         })
     }
+    /// Sizes the node using it's intrinsic size, measured for a proposed size.
+    ///
+    /// **This is primarily for UI elements such as text** which reflow in both axes.
+    /// The node is measured with:
+    /// - a proposed width of `0` for it's minimum width (the min-content size, such as the longest word)
+    /// - an unbounded proposal for it's maximum width (the max-content size, such as a single line)
+    /// - the width it's laid out in for it's height (the fitted size)
+    ///
+    /// The node's width stays between it's min-content & max-content widths,
+    /// so in a `row` text shares space with it's siblings & wraps when there isn't enough room.
+    /// ```rust
+    /// use backer::{models::*, nodes::*};
+    ///
+    /// // Each word is 10 wide & each line is 10 tall
+    /// let words = 5.;
+    /// let text = draw(|_, _: &mut ()| {}).measure(move |proposal, _| {
+    ///     let per_line = proposal
+    ///         .width
+    ///         .map_or(words, |width| (width / 10.).floor().clamp(1., words));
+    ///     MeasuredSize {
+    ///         width: per_line * 10.,
+    ///         height: (words / per_line).ceil() * 10.,
+    ///     }
+    /// });
+    /// ```
+    pub fn measure(self, f: impl Fn(ProposedSize, &mut State) -> MeasuredSize + 'static) -> Self {
+        self.wrap_or_update_explicit(Size {
+            measure: Some(Rc::new(f)),
+            ..Default::default()
+        })
+    }
 }
 
 impl<'nodes, State> Node<'nodes, State> {
@@ -271,15 +306,38 @@ impl<'nodes, State> Node<'nodes, State> {
             (Some(x), Some(y)) => self.x_align_contents(x).y_align_contents(y),
         }
     }
+    /// Reads the size of a scroll container's contents along it's scroll axis, see `nodes::scroll`
+    ///
+    /// The closure is passed the size of the contents & the size of the visible region of the scroll container.
+    ///
+    /// This modifier must be used immediately after the scroll node to take effect.
+    pub fn on_content_size(mut self, f: impl Fn(f32, f32, &mut State) + 'nodes) -> Self {
+        if let NodeValue::Scroll {
+            ref mut on_content_size,
+            ..
+        } = self.inner
+        {
+            *on_content_size = Some(Box::new(f));
+        }
+        self
+    }
     fn x_align_contents(mut self, align: XAlign) -> Self {
         match self.inner {
             NodeValue::Column {
                 off_axis_align: ref mut col_align,
                 ..
+            }
+            | NodeValue::VirtualList {
+                off_axis_align: ref mut col_align,
+                ..
             } => *col_align = Some(align),
             NodeValue::Row {
                 align: ref mut row_align,
                 ..
+            }
+            | NodeValue::Flow {
+                align: ref mut row_align,
+                ..
             } => *row_align = Some(align),
             NodeValue::Stack {
                 ref mut x_align, ..
@@ -293,10 +351,18 @@ impl<'nodes, State> Node<'nodes, State> {
             NodeValue::Row {
                 off_axis_align: ref mut row_align,
                 ..
+            }
+            | NodeValue::Flow {
+                off_axis_align: ref mut row_align,
+                ..
             } => *row_align = Some(align),
             NodeValue::Column {
                 align: ref mut col_align,
                 ..
+            }
+            | NodeValue::VirtualList {
+                align: ref mut col_align,
+                ..
             } => *col_align = Some(align),
             NodeValue::Stack {
                 ref mut y_align, ..
@@ -355,6 +421,28 @@ impl<'nodes, State> Node<'nodes, State> {
             ..Default::default()
         })
     }
+    /// Sets how much of the extra space in a row or column this node takes, relative to it's siblings.
+    ///
+    /// Nodes grow by a factor of `1.` by default,
+    /// so a node with `grow(3.)` takes three times as much extra space as it's siblings.
+    /// A factor of `0.` prevents the node from growing beyond it's lower bound.
+    pub fn grow(self, factor: f32) -> Self {
+        self.wrap_or_update_explicit(Size {
+            grow: Some(factor.max(0.)),
+            ..Default::default()
+        })
+    }
+    /// Sets how much of the missing space in a row or column this node gives up, relative to it's siblings.
+    ///
+    /// Nodes shrink by a factor of `1.` by default,
+    /// so a node with `shrink(3.)` gives up three times as much space as it's siblings when space is limited.
+    /// A factor of `0.` prevents the node from shrinking.
+    pub fn shrink(self, factor: f32) -> Self {
+        self.wrap_or_update_explicit(Size {
+            shrink: Some(factor.max(0.)),
+            ..Default::default()
+        })
+    }
     /// Attaches `node` over this node as an overlay
     ///
     /// The area available to the attached node is the size of the node it's attached to.
@@ -393,6 +481,436 @@ impl<'nodes, State> Node<'nodes, State> {
             },
         }
     }
+    /// Places the node in a specific cell of a grid, see `nodes::grid`
+    ///
+    /// Columns outside of the grid are clamped to it's last column,
+    /// while rows outside of the grid add rows as needed.
+    /// This modifier must be the last modifier applied to a grid element to take effect.
+    pub fn grid_cell(self, column: usize, row: usize) -> Self {
+        self.wrap_or_update_grid_item(GridPlacement {
+            column: Some(column),
+            row: Some(row),
+            ..Default::default()
+        })
+    }
+    /// Spans the node across multiple columns & rows of a grid, see `nodes::grid`
+    ///
+    /// Column spans are clamped to the columns remaining in the grid,
+    /// & row spans to the grid's rows plus one row for each element.
+    /// This modifier must be the last modifier applied to a grid element to take effect.
+    pub fn grid_span(self, columns: usize, rows: usize) -> Self {
+        self.wrap_or_update_grid_item(GridPlacement {
+            column_span: Some(columns),
+            row_span: Some(rows),
+            ..Default::default()
+        })
+    }
+    fn wrap_or_update_grid_item(mut self, update: GridPlacement) -> Self {
+        match self.inner {
+            NodeValue::GridItem {
+                ref mut placement,
+                element: _,
+            } => {
+                *placement = GridPlacement {
+                    column: update.column.or(placement.column),
+                    row: update.row.or(placement.row),
+                    column_span: update.column_span.or(placement.column_span),
+                    row_span: update.row_span.or(placement.row_span),
+                };
+            }
+            _ => {
+                return Node {
+                    inner: NodeValue::GridItem {
+                        placement: update,
+                        element: Box::new(NodeCache::new(self.inner)),
+                    },
+                };
+            }
+        }
+        self
+    }
+    /// Overrides the horizontal direction for this node's contents.
+    ///
+    /// See `Layout::with_direction`
+    pub fn direction(self, direction: Direction) -> Self {
+        Node {
+            inner: NodeValue::Direction {
+                direction,
+                element: Box::new(NodeCache::new(self.inner)),
+            },
+        }
+    }
+    /// Caches the constraints of this subtree between layout passes, see `LayoutCache`.
+    ///
+    /// The key must change whenever anything the subtree's size depends on changes.
+    /// While the key & the size of the available area are unchanged, constraints are reused
+    /// without being recalculated & without calling `dynamic` or `area_reader` closures to measure the subtree.
+    ///
+    /// When the layout is retained between passes, an unchanged subtree laid out in the same area is also laid out
+    /// from the nodes built in the previous pass, without rebuilding or measuring any of them.
+    /// The subtree is still walked to record ids & handlers, & drawn every pass.
+    /// Since the key can't change within a retained tree, use [`Node::cache_key_with`] there.
+    pub fn cache_key(self, key: u64) -> Self {
+        Node {
+            inner: NodeValue::Cached {
+                key: CacheKey::Fixed(key),
+                element: Box::new(NodeCache::new(self.inner)),
+                laid_out: None,
+            },
+        }
+    }
+    /// Caches this subtree using a key read from the state every pass, see [`Node::cache_key`]
+    pub fn cache_key_with(self, key: impl Fn(&State) -> u64 + 'nodes) -> Self {
+        Node {
+            inner: NodeValue::Cached {
+                key: CacheKey::Computed(Box::new(key)),
+                element: Box::new(NodeCache::new(self.inner)),
+                laid_out: None,
+            },
+        }
+    }
+    /// Marks the node with a stable identifier.
+    ///
+    /// The area calculated for this node can be read after layout using `Layout::area_of`.
+    /// Integer ids match by value regardless of their type, see [`NodeId::new`]
+    pub fn id(self, id: impl Hash) -> Self {
+        Node {
+            inner: NodeValue::Id {
+                id: NodeId::new(id),
+                lifecycle: Lifecycle::default(),
+                element: Box::new(NodeCache::new(self.inner)),
+            },
+        }
+    }
+    /// Called after a layout pass which finds this node's id, when the previous pass didn't.
+    ///
+    /// Nodes in `visible(false)` subtrees are still found, but nodes outside of
+    /// the visible range of a `virtual_list` are not.
+    /// Marks the node with `id` as if by [`Node::id`], which may already be applied directly before this modifier.
+    ///
+    /// The ids found in the previous pass are retained by the layout's [`crate::ComponentStore`].
+    /// When a new layout is created every frame, pass the store on using [`crate::Layout::with_store`],
+    /// otherwise this is called in every pass.
+    ///
+    /// ```rust
+    /// use backer::models::*;
+    /// use backer::nodes::*;
+    /// use backer::Layout;
+    ///
+    /// let mut layout = Layout::new(dynamic(|shown: &mut Vec<&str>| {
+    ///     space()
+    ///         .on_appear("card", |shown: &mut Vec<&str>| shown.push("appeared"))
+    ///         .on_disappear("card", |shown: &mut Vec<&str>| shown.push("disappeared"))
+    /// }));
+    /// let mut shown = vec![];
+    /// layout.layout(Area::new(0., 0., 100., 100.), &mut shown);
+    /// layout.layout(Area::new(0., 0., 100., 100.), &mut shown);
+    /// assert_eq!(shown, vec!["appeared"]);
+    /// ```
+    pub fn on_appear(self, id: impl Hash, f: impl Fn(&mut State) + 'nodes) -> Self {
+        self.with_lifecycle(NodeId::new(id), |lifecycle| {
+            lifecycle.on_appear = Some(Rc::new(f))
+        })
+    }
+    /// Called after a layout pass which doesn't find this node's id, when the previous pass did.
+    ///
+    /// The callback is retained by the layout's [`crate::ComponentStore`] after the pass which found the node,
+    /// so when a new layout is created every frame, pass the store on using [`crate::Layout::with_store`].
+    /// Inside of a `nodes::scope`, the callback is retained along with the scope's position in the tree,
+    /// so it's dropped without being called if the scope itself moves or disappears.
+    /// Marks the node with `id`, see [`Node::on_appear`].
+    pub fn on_disappear(self, id: impl Hash, f: impl Fn(&mut State) + 'static) -> Self {
+        self.with_lifecycle(NodeId::new(id), |lifecycle| {
+            lifecycle.on_disappear = Some(Rc::new(f))
+        })
+    }
+    /// Updates the lifecycle of this node if it's already marked with `id`,
+    /// otherwise marks it with `id` first
+    fn with_lifecycle(
+        self,
+        id: NodeId,
+        update: impl FnOnce(&mut Lifecycle<'nodes, State>),
+    ) -> Self {
+        let mut node = match self.inner {
+            NodeValue::Id { id: current, .. } if current == id => self,
+            inner => Node {
+                inner: NodeValue::Id {
+                    id,
+                    lifecycle: Lifecycle::default(),
+                    element: Box::new(NodeCache::new(inner)),
+                },
+            },
+        };
+        if let NodeValue::Id {
+            ref mut lifecycle, ..
+        } = node.inner
+        {
+            update(lifecycle);
+        }
+        node
+    }
+    /// Animates this node from it's previous area to it's new area whenever it's area changes,
+    /// over `duration` seconds.
+    ///
+    /// Animations only progress when laid out with [`crate::Layout::layout_at`] or [`crate::Layout::draw_at`].
+    /// The animation's progress is retained by the layout's [`crate::ComponentStore`] using `key`,
+    /// which should be unique within the layout.
+    /// Drawables are told the animation's progress, see `models::DrawCtx` & `nodes::draw_transition`.
+    ///
+    /// ```rust
+    /// use backer::models::*;
+    /// use backer::nodes::*;
+    /// use backer::Layout;
+    ///
+    /// let bar = |width| {
+    ///     space()
+    ///         .width(width)
+    ///         .id("bar")
+    ///         .animate("bar", Curve::Linear, 1.)
+    ///         .align(Align::Leading)
+    /// };
+    /// let mut layout = Layout::new(bar(20.));
+    /// layout.layout_at(Area::new(0., 0., 100., 100.), 0., &mut ());
+    ///
+    /// // The animation starts when the node's area changes
+    /// let mut layout = Layout::new(bar(60.)).with_store(layout.take_store());
+    /// layout.layout_at(Area::new(0., 0., 100., 100.), 1., &mut ());
+    /// layout.layout_at(Area::new(0., 0., 100., 100.), 1.5, &mut ());
+    /// assert_eq!(layout.area_of("bar").unwrap().width, 40.);
+    /// assert!(layout.is_animating());
+    /// ```
+    pub fn animate(self, key: impl Hash, curve: Curve, duration: f32) -> Self {
+        Node {
+            inner: NodeValue::Animate {
+                key: NodeId::new(key),
+                curve,
+                duration,
+                element: Box::new(NodeCache::new(self.inner)),
+            },
+        }
+    }
+    /// Flies this node from the area of the node with the same `key` in the previous layout pass,
+    /// even when the two nodes are in different subtrees, over `duration` seconds.
+    ///
+    /// Nodes whose key wasn't found in the previous pass fade in,
+    /// & nodes whose key isn't found fade out, see [`crate::Layout::departures`].
+    /// Drawables are told the fading node's opacity, see `models::DrawCtx`.
+    /// Keys should be unique within each layout pass.
+    ///
+    /// Transitions only progress when laid out with [`crate::Layout::layout_at`] or [`crate::Layout::draw_at`].
+    ///
+    /// ```rust
+    /// use backer::models::*;
+    /// use backer::nodes::*;
+    /// use backer::Layout;
+    ///
+    /// let thumbnail = || space().id("thumbnail").matched_geometry("photo", Curve::Linear, 1.);
+    /// // The thumbnail starts in a list cell
+    /// let mut layout = Layout::new(row(vec![thumbnail().width(20.).height(20.), space()]));
+    /// layout.layout_at(Area::new(0., 0., 100., 100.), 0., &mut ());
+    ///
+    /// // & flies to a detail view
+    /// let mut layout = Layout::new(column(vec![space(), thumbnail().height(50.)]))
+    ///     .with_store(layout.take_store());
+    /// layout.layout_at(Area::new(0., 0., 100., 100.), 1., &mut ());
+    /// layout.layout_at(Area::new(0., 0., 100., 100.), 1.5, &mut ());
+    /// assert!(layout.is_animating());
+    /// layout.layout_at(Area::new(0., 0., 100., 100.), 2., &mut ());
+    /// assert_eq!(layout.area_of("thumbnail"), Some(Area::new(0., 50., 100., 50.)));
+    /// ```
+    pub fn matched_geometry(self, key: impl Hash, curve: Curve, duration: f32) -> Self {
+        Node {
+            inner: NodeValue::MatchedGeometry {
+                key: NodeId::new(key),
+                curve,
+                duration,
+                element: Box::new(NodeCache::new(self.inner)),
+            },
+        }
+    }
+    /// Handles pointer events which land in this node's area, see `Layout::dispatch`
+    ///
+    /// The handler receives the event & the area of this node.
+    /// Events bubble from the innermost handler outwards,
+    /// returning [`Propagation::Stop`] prevents enclosing handlers from receiving the event.
+    ///
+    /// ```rust
+    /// use backer::models::*;
+    /// use backer::nodes::*;
+    /// use backer::Layout;
+    ///
+    /// let mut layout = Layout::new(
+    ///     space()
+    ///         .width(50.)
+    ///         .on_pointer(|event, _area, clicks: &mut usize| match event {
+    ///             PointerEvent::Down { .. } => {
+    ///                 *clicks += 1;
+    ///                 Propagation::Stop
+    ///             }
+    ///             _ => Propagation::Continue,
+    ///         }),
+    /// );
+    /// let mut clicks = 0;
+    /// layout.layout(Area::new(0., 0., 100., 100.), &mut clicks);
+    /// let down = |x| PointerEvent::Down {
+    ///     x,
+    ///     y: 50.,
+    ///     button: PointerButton::Primary,
+    /// };
+    /// layout.dispatch(down(10.), &mut clicks);
+    /// layout.dispatch(down(50.), &mut clicks);
+    /// assert_eq!(clicks, 1);
+    /// ```
+    pub fn on_pointer(
+        self,
+        handler: impl Fn(PointerEvent, Area, &mut State) -> Propagation + 'nodes,
+    ) -> Self {
+        Node {
+            inner: NodeValue::Pointer {
+                handler: Box::new(handler),
+                element: Box::new(NodeCache::new(self.inner)),
+                target: None,
+            },
+        }
+    }
+    /// Calls `f` when the primary pointer button is released over this node, see [`Node::on_pointer`]
+    ///
+    /// Clicks don't bubble to enclosing handlers.
+    pub fn on_click(self, f: impl Fn(Area, &mut State) + 'nodes) -> Self {
+        self.on_pointer(move |event, area, state| match event {
+            PointerEvent::Up {
+                button: PointerButton::Primary,
+                ..
+            } => {
+                f(area, state);
+                Propagation::Stop
+            }
+            _ => Propagation::Continue,
+        })
+    }
+    /// Calls `f` with `true` when the pointer enters this node & `false` when it leaves, see [`Node::on_pointer`]
+    ///
+    /// Hover is updated when events are passed to `Layout::dispatch`.
+    pub fn on_hover(self, f: impl Fn(bool, &mut State) + 'nodes) -> Self {
+        self.on_pointer(move |event, _, state| {
+            match event {
+                PointerEvent::Enter { .. } => f(true, state),
+                PointerEvent::Leave { .. } => f(false, state),
+                _ => (),
+            }
+            Propagation::Continue
+        })
+    }
+    /// Allows the node to receive keyboard focus, see `Layout::focus_next`
+    ///
+    /// Drawables inside the focused node are told they are focused,
+    /// see `nodes::draw_with_ctx` & `traits::Drawable::draw_with_ctx`.
+    /// Nodes in `visible(false)` subtrees can't be focused.
+    ///
+    /// ```rust
+    /// use backer::models::*;
+    /// use backer::nodes::*;
+    /// use backer::Layout;
+    ///
+    /// let mut layout = Layout::new(column(vec![
+    ///     space().focusable("name"),
+    ///     space().focusable("email"),
+    /// ]));
+    /// layout.layout(Area::new(0., 0., 100., 100.), &mut ());
+    /// assert_eq!(layout.focus_next(), Some(NodeId::new("name")));
+    /// assert_eq!(layout.focus_next(), Some(NodeId::new("email")));
+    /// ```
+    pub fn focusable(self, id: impl Hash) -> Self {
+        self.focusable_with(id, FocusOptions::default())
+    }
+    /// Allows the node to receive keyboard focus, with a tab index or focus neighbors, see [`FocusOptions`]
+    ///
+    /// ```rust
+    /// use backer::models::*;
+    /// use backer::nodes::*;
+    /// use backer::Layout;
+    ///
+    /// let mut layout = Layout::new(column(vec![
+    ///     space().focusable("name"),
+    ///     space().focusable_with(
+    ///         "email",
+    ///         FocusOptions {
+    ///             tab_index: Some(1),
+    ///             ..Default::default()
+    ///         },
+    ///     ),
+    /// ]));
+    /// layout.layout(Area::new(0., 0., 100., 100.), &mut ());
+    /// assert_eq!(layout.focus_next(), Some(NodeId::new("email")));
+    /// ```
+    pub fn focusable_with(self, id: impl Hash, options: FocusOptions) -> Self {
+        Node {
+            inner: NodeValue::Focusable {
+                id: NodeId::new(id),
+                tab_index: options.tab_index,
+                neighbors: options.neighbors,
+                element: Box::new(NodeCache::new(self.inner)),
+            },
+        }
+    }
+    /// Keeps focus within the focusable nodes of this subtree, like a modal dialog.
+    ///
+    /// Once a node inside the scope is focused, `Layout::focus_next` & `Layout::focus_prev` cycle within the scope.
+    /// Tab indices are ordered within their scope & the scope is placed in it's parent's tab order by tree order.
+    pub fn focus_scope(self) -> Self {
+        Node {
+            inner: NodeValue::FocusScope {
+                element: Box::new(NodeCache::new(self.inner)),
+            },
+        }
+    }
+    /// Describes the node to assistive technology, see `Layout::accessibility_tree`
+    ///
+    /// ```rust
+    /// use backer::models::*;
+    /// use backer::nodes::*;
+    /// use backer::Layout;
+    ///
+    /// let mut layout = Layout::new(
+    ///     row(vec![
+    ///         space().semantics(Role::Text, "Volume"),
+    ///         space().semantics_with_value(Role::Slider, "Volume", "50%"),
+    ///     ])
+    ///     .semantics(Role::Group, "Settings"),
+    /// );
+    /// layout.layout(Area::new(0., 0., 100., 100.), &mut ());
+    /// let tree = layout.accessibility_tree();
+    /// assert_eq!(tree[0].label, "Settings");
+    /// assert_eq!(tree[0].children[1].value.as_deref(), Some("50%"));
+    /// assert_eq!(tree[0].children[1].bounds, Area::new(50., 0., 50., 100.));
+    /// ```
+    pub fn semantics(self, role: Role, label: impl Into<String>) -> Self {
+        Node {
+            inner: NodeValue::Semantics {
+                role,
+                label: label.into(),
+                value: None,
+                element: Box::new(NodeCache::new(self.inner)),
+            },
+        }
+    }
+    /// Describes the node to assistive technology along with it's current value,
+    /// such as the contents of a text field, see [`Node::semantics`]
+    pub fn semantics_with_value(
+        self,
+        role: Role,
+        label: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        Node {
+            inner: NodeValue::Semantics {
+                role,
+                label: label.into(),
+                value: Some(value.into()),
+                element: Box::new(NodeCache::new(self.inner)),
+            },
+        }
+    }
     fn wrap_or_update_explicit(mut self, size: Size<State>) -> Self {
         match self.inner {
             NodeValue::Explicit {
@@ -427,8 +945,11 @@ impl<'nodes, State> Node<'nodes, State> {
                     aspect: size.aspect.or(options.aspect),
                     dynamic_height: size.dynamic_height.or(options.dynamic_height.clone()),
                     dynamic_width: size.dynamic_width.or(options.dynamic_width.clone()),
+                    measure: size.measure.or(options.measure.clone()),
                     expand_x: size.expand_x || options.expand_x,
                     expand_y: size.expand_y || options.expand_y,
+                    grow: size.grow.or(options.grow),
+                    shrink: size.shrink.or(options.shrink),
                 };
             }
             _ => {
@@ -1,7 +1,8 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, mem};
 
 use crate::{
     constraints::SizeConstraints,
+    context::LayoutCtx,
     layout::NodeValue,
     models::{Area, XAlign, YAlign},
 };
@@ -10,6 +11,11 @@ pub(crate) struct NodeCache<'nodes, State> {
     pub(crate) kind: NodeValue<'nodes, State>,
     pub(crate) cache_area: Option<Area>,
     pub(crate) cached_constraints: Option<SizeConstraints>,
+    /// Whether the cached constraints depend on the area they were measured in,
+    /// such as the height of wrapping text
+    pub(crate) area_dependent: bool,
+    /// The pass the cached constraints were measured in, see `NodeCache::is_current`
+    pub(crate) pass: u64,
 }
 
 impl<'nodes, State> NodeCache<'nodes, State> {
@@ -18,8 +24,17 @@ impl<'nodes, State> NodeCache<'nodes, State> {
             kind,
             cache_area: None,
             cached_constraints: None,
+            area_dependent: false,
+            pass: 0,
         }
     }
+    /// Whether the node was measured in the pass being laid out.
+    ///
+    /// Measurements from earlier passes are only reused inside of an unchanged cached subtree,
+    /// otherwise the state they were measured with may have changed.
+    pub(crate) fn is_current(&self, ctx: &LayoutCtx) -> bool {
+        self.pass == ctx.pass || ctx.reusing
+    }
 }
 
 impl<State> Debug for NodeCache<'_, State> {
@@ -28,6 +43,8 @@ impl<State> Debug for NodeCache<'_, State> {
             .field("kind", &self.kind)
             .field("cache_area", &self.cache_area)
             .field("cached_constraints", &self.cached_constraints)
+            .field("area_dependent", &self.area_dependent)
+            .field("pass", &self.pass)
             .finish()
     }
 }
@@ -37,15 +54,25 @@ impl<State> NodeCache<'_, State> {
         &mut self,
         available_area: Area,
         state: &mut State,
+        ctx: &mut LayoutCtx,
     ) -> Option<SizeConstraints> {
-        if let (Some(cache), Some(constraints)) = (self.cache_area, self.cached_constraints) {
-            if cache == available_area {
-                return Some(constraints);
+        ctx.enter_measure();
+        let outer_dependent = mem::take(&mut ctx.area_dependent);
+        let constraints = match (self.cache_area, self.cached_constraints) {
+            (Some(cache), Some(constraints)) if cache == available_area && self.is_current(ctx) => {
+                Some(constraints)
             }
-        }
-        let constraints = self.kind.constraints(available_area, state);
-        self.cache_area = Some(available_area);
-        self.cached_constraints = constraints;
+            _ => {
+                let constraints = self.kind.constraints(available_area, state, ctx);
+                self.pass = ctx.pass;
+                self.cache_area = Some(available_area);
+                self.cached_constraints = constraints;
+                self.area_dependent = ctx.area_dependent;
+                constraints
+            }
+        };
+        ctx.area_dependent = outer_dependent || self.area_dependent;
+        ctx.exit_measure();
         constraints
     }
     pub(crate) fn layout(
@@ -54,12 +81,18 @@ impl<State> NodeCache<'_, State> {
         contextual_x_align: Option<XAlign>,
         contextual_y_align: Option<YAlign>,
         state: &mut State,
+        ctx: &mut LayoutCtx,
     ) {
+        let current = self.is_current(ctx);
+        if let Some(dump) = &mut ctx.dump {
+            dump.pending_constraints = self.cached_constraints.filter(|_| current);
+        }
         self.kind.layout(
             available_area,
             contextual_x_align,
             contextual_y_align,
             state,
+            ctx,
         );
     }
     pub(crate) fn draw(&mut self, state: &mut State, contextual_visibility: bool) {
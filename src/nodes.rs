@@ -1,12 +1,15 @@
 use crate::{
     drawable::{DrawableNode, SomeDrawable},
+    grid::place_cells,
     layout::NodeValue,
     models::*,
     node_cache::NodeCache,
     scoper::{ScopeCtx, ScopeCtxResult, Scoper},
-    traits::Drawable,
+    traits::{Drawable, LayoutAlgorithm, TransitionDrawable},
+    virtual_list::ItemExtents,
     Node,
 };
+use std::hash::Hash;
 
 macro_rules! container_doc {
     () => {
@@ -53,7 +56,7 @@ pub fn column<State>(elements: Vec<Node<'_, State>>) -> Node<'_, State> {
 ///     ),
 /// ]);
 /// ```
-pub fn group<State>(elements: Vec<Node<State>>) -> Node<'_, State> {
+pub fn group<State>(elements: Vec<Node<'_, State>>) -> Node<'_, State> {
     Node {
         inner: NodeValue::Group(filter_empty(ungroup(elements))),
     }
@@ -61,7 +64,7 @@ pub fn group<State>(elements: Vec<Node<State>>) -> Node<'_, State> {
 /// Creates a vertical sequence of elements with the specified spacing between each element.
 ///
 #[doc = container_doc!()]
-pub fn column_spaced<State>(spacing: f32, elements: Vec<Node<State>>) -> Node<'_, State> {
+pub fn column_spaced<State>(spacing: f32, elements: Vec<Node<'_, State>>) -> Node<'_, State> {
     Node {
         inner: NodeValue::Column {
             elements: filter_empty(ungroup(elements)),
@@ -74,7 +77,7 @@ pub fn column_spaced<State>(spacing: f32, elements: Vec<Node<State>>) -> Node<'_
 /// Creates a horizontal sequence of elements
 ///
 #[doc = container_doc!()]
-pub fn row<State>(elements: Vec<Node<State>>) -> Node<'_, State> {
+pub fn row<State>(elements: Vec<Node<'_, State>>) -> Node<'_, State> {
     Node {
         inner: NodeValue::Row {
             elements: filter_empty(ungroup(elements)),
@@ -87,7 +90,7 @@ pub fn row<State>(elements: Vec<Node<State>>) -> Node<'_, State> {
 /// Creates a horizontal sequence of elements with the specified spacing between each element.
 ///
 #[doc = container_doc!()]
-pub fn row_spaced<State>(spacing: f32, elements: Vec<Node<State>>) -> Node<'_, State> {
+pub fn row_spaced<State>(spacing: f32, elements: Vec<Node<'_, State>>) -> Node<'_, State> {
     Node {
         inner: NodeValue::Row {
             elements: filter_empty(ungroup(elements)),
@@ -100,7 +103,7 @@ pub fn row_spaced<State>(spacing: f32, elements: Vec<Node<State>>) -> Node<'_, S
 /// Creates a sequence of elements to be laid out on top of each other.
 ///
 #[doc = container_doc!()]
-pub fn stack<State>(elements: Vec<Node<State>>) -> Node<'_, State> {
+pub fn stack<State>(elements: Vec<Node<'_, State>>) -> Node<'_, State> {
     Node {
         inner: NodeValue::Stack {
             elements: filter_empty(ungroup(elements)),
@@ -109,6 +112,306 @@ pub fn stack<State>(elements: Vec<Node<State>>) -> Node<'_, State> {
         },
     }
 }
+/// Creates a container which is laid out by a user-defined [`LayoutAlgorithm`]
+///
+/// Each element is measured in the container's available area, the algorithm then measures the container
+/// & places each element. Elements are laid out within the area they are placed in,
+/// respecting their own size modifiers.
+///
+/// Hidden elements, see [`Node::visible`], aren't passed to the algorithm & are laid out with zero size.
+/// ```rust
+/// use backer::{models::*, nodes::*, traits::LayoutAlgorithm};
+///
+/// /// Places each element on the diagonal of the container
+/// struct Diagonal;
+///
+/// impl LayoutAlgorithm for Diagonal {
+///     fn measure(&self, _available_area: Area, children: &[Constraints]) -> Constraints {
+///         let min_width = children.iter().filter_map(|child| child.width.min).sum();
+///         Constraints {
+///             width: Bounds::at_least(min_width),
+///             height: Bounds::none(),
+///         }
+///     }
+///     fn place(&self, available_area: Area, children: &[Constraints]) -> Vec<Area> {
+///         let count = children.len() as f32;
+///         (0..children.len())
+///             .map(|i| Area {
+///                 x: available_area.x + available_area.width / count * i as f32,
+///                 y: available_area.y + available_area.height / count * i as f32,
+///                 width: available_area.width / count,
+///                 height: available_area.height / count,
+///             })
+///             .collect()
+///     }
+/// }
+///
+/// let layout = custom::<()>(Diagonal, vec![space(), space()]);
+/// ```
+pub fn custom<'nodes, State>(
+    algorithm: impl LayoutAlgorithm + 'nodes,
+    elements: Vec<Node<'nodes, State>>,
+) -> Node<'nodes, State> {
+    Node {
+        inner: NodeValue::Custom {
+            algorithm: Box::new(algorithm),
+            elements: filter_empty(ungroup(elements)),
+        },
+    }
+}
+/// Creates a horizontal sequence of elements which wraps onto a new line
+/// when it runs out of width.
+///
+/// Each element is measured using it's own constraints, so elements generally
+/// need an explicit or bounded width to share a line.
+/// Unconstrained elements take up an entire line.
+///
+#[doc = container_doc!()]
+pub fn flow<State>(elements: Vec<Node<'_, State>>) -> Node<'_, State> {
+    Node {
+        inner: NodeValue::Flow {
+            elements: filter_empty(ungroup(elements)),
+            spacing: 0.,
+            line_spacing: 0.,
+            align: None,
+            off_axis_align: None,
+        },
+    }
+}
+/// Creates a wrapping horizontal sequence of elements with `spacing` between each element in a line
+/// & `line_spacing` between each line.
+///
+/// See [`flow`]
+///
+#[doc = container_doc!()]
+pub fn flow_spaced<State>(
+    spacing: f32,
+    line_spacing: f32,
+    elements: Vec<Node<'_, State>>,
+) -> Node<'_, State> {
+    Node {
+        inner: NodeValue::Flow {
+            elements: filter_empty(ungroup(elements)),
+            spacing,
+            line_spacing,
+            align: None,
+            off_axis_align: None,
+        },
+    }
+}
+/// Creates a grid of elements, sized by `columns` & `rows` tracks.
+///
+/// Elements are placed in row-major order into the next free cell,
+/// unless they are placed explicitly using [`Node::grid_cell`].
+/// Elements can span multiple tracks using [`Node::grid_span`].
+/// Additional rows are added as needed using [`Track::Fit`],
+/// & a grid without any `columns` has a single [`Track::Fit`] column.
+///
+/// Only elements which span a single track contribute to the size of that track.
+/// ```rust
+/// use backer::models::*;
+/// use backer::nodes::*;
+///
+/// grid::<()>(
+///     vec![Track::Fixed(100.), Track::Fraction(1.), Track::Fraction(3.)],
+///     vec![Track::Fit, Track::Range { min: Some(20.), max: None }],
+///     vec![
+///         draw(|_, _| {}).grid_span(3, 1),
+///         draw(|_, _| {}).grid_cell(1, 1),
+///     ],
+/// );
+/// ```
+pub fn grid<State>(
+    columns: Vec<Track>,
+    rows: Vec<Track>,
+    elements: Vec<Node<'_, State>>,
+) -> Node<'_, State> {
+    grid_spaced(0., 0., columns, rows, elements)
+}
+/// Creates a grid of elements with `column_gap` between each column & `row_gap` between each row.
+///
+/// See [`grid`]
+pub fn grid_spaced<State>(
+    column_gap: f32,
+    row_gap: f32,
+    mut columns: Vec<Track>,
+    mut rows: Vec<Track>,
+    elements: Vec<Node<'_, State>>,
+) -> Node<'_, State> {
+    if columns.is_empty() {
+        columns.push(Track::Fit);
+    }
+    let (placements, elements): (Vec<GridPlacement>, Vec<NodeCache<State>>) =
+        filter_empty(ungroup(elements))
+            .into_iter()
+            .map(|element| match element.kind {
+                NodeValue::GridItem { placement, element } => (placement, *element),
+                _ => (GridPlacement::default(), element),
+            })
+            .unzip();
+    let (cells, row_count) = place_cells(&placements, columns.len(), rows.len());
+    rows.resize(row_count, Track::Fit);
+    Node {
+        inner: NodeValue::Grid {
+            elements,
+            cells,
+            columns,
+            rows,
+            column_gap,
+            row_gap,
+        },
+    }
+}
+/// Creates a container which scrolls it's contents along `axis`.
+///
+/// The contents are measured without a bound along `axis` & laid out at their natural size
+/// (which may be larger than the scroll container), then shifted by `offset`.
+/// Contents without a natural size along `axis` fill the scroll container.
+/// A positive `offset` moves the contents towards the leading / top edge.
+///
+/// Drawables which fall outside of the scroll container are drawn with `visible: false`
+/// (closures passed to [`draw`] are not called at all).
+///
+/// Use [`Node::on_content_size`] to read the size of the contents, for example to clamp `offset`.
+/// ```rust
+/// use backer::models::*;
+/// use backer::nodes::*;
+///
+/// struct MyState {
+///     offset: f32,
+///     content_size: f32,
+/// }
+///
+/// dynamic(|state: &mut MyState| {
+///     scroll(
+///         Axis::Vertical,
+///         state.offset,
+///         column(
+///             (0..100)
+///                 .map(|_| draw(|_, _: &mut MyState| {}).height(20.))
+///                 .collect(),
+///         ),
+///     )
+///     .on_content_size(|content_size, _viewport_size, state: &mut MyState| {
+///         state.content_size = content_size
+///     })
+/// });
+/// ```
+pub fn scroll<State>(axis: Axis, offset: f32, element: Node<'_, State>) -> Node<'_, State> {
+    Node {
+        inner: NodeValue::Scroll {
+            axis,
+            offset,
+            element: Box::new(NodeCache::new(element.inner)),
+            on_content_size: None,
+        },
+    }
+}
+/// Creates a vertical list of `count` elements which only builds the elements that are visible.
+///
+/// `extent` returns the height of the element at an index, & is used to size the list without building it's elements.
+/// It's called once per element during each layout pass, use [`virtual_list_uniform`] to avoid this
+/// when every element has the same height.
+/// `builder` is only called for the indices of elements which intersect the visible region -
+/// either the viewport of an enclosing [`scroll`] or the list itself.
+///
+/// Elements are laid out like a [`fn@column`], & support the same alignment using [`Node::align_contents`]
+/// ```rust
+/// use backer::models::*;
+/// use backer::nodes::*;
+///
+/// struct MyState {
+///     offset: f32,
+///     items: Vec<String>,
+/// }
+///
+/// dynamic(|state: &mut MyState| {
+///     scroll(
+///         Axis::Vertical,
+///         state.offset,
+///         virtual_list(
+///             state.items.len(),
+///             |_, _| 20.,
+///             |i, _| draw(move |_, state: &mut MyState| println!("{}", state.items[i])),
+///         ),
+///     )
+/// });
+/// ```
+pub fn virtual_list<'nodes, State>(
+    count: usize,
+    extent: impl Fn(usize, &mut State) -> f32 + 'nodes,
+    builder: impl Fn(usize, &mut State) -> Node<'nodes, State> + 'nodes,
+) -> Node<'nodes, State> {
+    virtual_list_spaced(0., count, extent, builder)
+}
+/// Creates a virtualized vertical list of elements with the specified spacing between each element.
+///
+/// See [`virtual_list`]
+pub fn virtual_list_spaced<'nodes, State>(
+    spacing: f32,
+    count: usize,
+    extent: impl Fn(usize, &mut State) -> f32 + 'nodes,
+    builder: impl Fn(usize, &mut State) -> Node<'nodes, State> + 'nodes,
+) -> Node<'nodes, State> {
+    Node {
+        inner: NodeValue::VirtualList {
+            count,
+            spacing,
+            extents: ItemExtents::Each {
+                extent: Box::new(extent),
+                items: None,
+            },
+            builder: Box::new(builder),
+            align: None,
+            off_axis_align: None,
+            computed: Vec::new(),
+        },
+    }
+}
+/// Creates a virtualized vertical list of `count` elements which are each `extent` tall.
+///
+/// Unlike [`virtual_list`], the cost of each layout pass doesn't depend on `count`.
+/// ```rust
+/// use backer::models::*;
+/// use backer::nodes::*;
+///
+/// scroll(
+///     Axis::Vertical,
+///     1_000_000.,
+///     virtual_list_uniform(10_000_000, 20., |i, _: &mut ()| {
+///         draw(move |_, _| println!("{i}"))
+///     }),
+/// );
+/// ```
+pub fn virtual_list_uniform<'nodes, State>(
+    count: usize,
+    extent: f32,
+    builder: impl Fn(usize, &mut State) -> Node<'nodes, State> + 'nodes,
+) -> Node<'nodes, State> {
+    virtual_list_uniform_spaced(0., count, extent, builder)
+}
+/// Creates a virtualized vertical list of `count` elements which are each `extent` tall,
+/// with the specified spacing between each element.
+///
+/// See [`virtual_list_uniform`]
+pub fn virtual_list_uniform_spaced<'nodes, State>(
+    spacing: f32,
+    count: usize,
+    extent: f32,
+    builder: impl Fn(usize, &mut State) -> Node<'nodes, State> + 'nodes,
+) -> Node<'nodes, State> {
+    Node {
+        inner: NodeValue::VirtualList {
+            count,
+            spacing,
+            extents: ItemExtents::Uniform(extent),
+            builder: Box::new(builder),
+            align: None,
+            off_axis_align: None,
+            computed: Vec::new(),
+        },
+    }
+}
 /// Creates a node that can be drawn.
 ///
 /// This node is the point of integration with the UI library of your choice.
@@ -128,25 +431,48 @@ pub fn stack<State>(elements: Vec<Node<State>>) -> Node<'_, State> {
 /// ```
 pub fn draw<'nodes, State>(
     drawable_fn: impl Fn(Area, &mut State) + 'static,
+) -> Node<'nodes, State> {
+    drawable_node(SomeDrawable::Fn(Box::new(drawable_fn)))
+}
+pub(crate) fn drawable_node<'nodes, State>(
+    drawable: SomeDrawable<'nodes, State>,
 ) -> Node<'nodes, State> {
     Node {
         inner: NodeValue::Draw(DrawableNode {
             area: Area::default(),
-            drawable: SomeDrawable::Fn(Box::new(drawable_fn)),
+            clipped: false,
+            focused: false,
+            progress: 1.,
+            opacity: 1.,
+            drawable,
         }),
     }
 }
+/// Creates a node that can be drawn, which is also told the context it's drawn in,
+/// such as whether it's inside the focused node or the progress of an enclosing animation.
+///
+/// See [`draw`], `models::DrawCtx`, `Node::focusable` & `Node::animate`
+pub fn draw_with_ctx<'nodes, State>(
+    drawable_fn: impl Fn(Area, &mut State, DrawCtx) + 'static,
+) -> Node<'nodes, State> {
+    drawable_node(SomeDrawable::CtxFn(Box::new(drawable_fn)))
+}
 /// Creates a node that can be drawn using an object which implements the `Drawable` trait
-/// (or the `TransitionDrawable` trait)
 ///
-/// See [`draw`]
+/// See [`draw`] & `traits::Drawable::draw_with_ctx`,
+/// or [`draw_transition`] for objects which implement the `TransitionDrawable` trait
 pub fn draw_object<'nodes, State>(drawable: impl Drawable<State> + 'nodes) -> Node<'nodes, State> {
-    Node {
-        inner: NodeValue::Draw(DrawableNode {
-            area: Area::default(),
-            drawable: SomeDrawable::Object(Box::new(drawable)),
-        }),
-    }
+    drawable_node(SomeDrawable::Object(Box::new(drawable)))
+}
+
+/// Creates a node that can be drawn using an object which implements the `TransitionDrawable` trait,
+/// which is told the progress of the innermost enclosing animation.
+///
+/// See [`draw_object`] & `Node::animate`
+pub fn draw_transition<'nodes, State>(
+    drawable: impl TransitionDrawable<State> + 'nodes,
+) -> Node<'nodes, State> {
+    drawable_node(SomeDrawable::Transition(Box::new(drawable)))
 }
 
 /// Creates an empty space which is laid out the same as any other node.
@@ -175,6 +501,7 @@ pub fn area_reader<'nodes, State>(
     Node {
         inner: NodeValue::AreaReader {
             read: Box::new(func),
+            computed: None,
         },
     }
 }
@@ -189,6 +516,51 @@ pub fn dynamic<'nodes, State>(
         },
     }
 }
+/// Returns nodes built using a local value which persists between layout passes
+///
+/// The value is created using `init` the first time `key` is laid out & is retained by the
+/// [`crate::Layout`] while a node with the same key remains in the tree, see [`crate::ComponentStore`].
+/// Keys must be unique within the tree, like [`Node::id`].
+///
+/// Like `dynamic`, `builder` may be called more than once per layout pass.
+///
+///```rust
+/// use backer::*;
+/// use backer::models::*;
+/// use backer::nodes::*;
+///
+/// let tree = || {
+///     stateful(
+///         "counter",
+///         || 0,
+///         |count: &mut usize, laid_out: &mut Vec<usize>| {
+///             *count += 1;
+///             laid_out.push(*count);
+///             space()
+///         },
+///     )
+/// };
+/// let mut laid_out = vec![];
+/// let mut layout = Layout::new(tree());
+/// layout.layout(Area::new(0., 0., 100., 100.), &mut laid_out);
+/// // The store can be moved to a new layout
+/// let mut layout = Layout::new(tree()).with_store(layout.take_store());
+/// layout.layout(Area::new(0., 0., 100., 100.), &mut laid_out);
+/// assert!(laid_out.windows(2).all(|pair| pair[0] < pair[1]));
+///```
+pub fn stateful<'nodes, State, T: 'static>(
+    key: impl Hash,
+    init: impl Fn() -> T + 'nodes,
+    builder: impl Fn(&mut T, &mut State) -> Node<'nodes, State> + 'nodes,
+) -> Node<'nodes, State> {
+    let key = NodeId::new(key);
+    Node {
+        inner: NodeValue::Stateful {
+            node: Box::new(move |store, state| builder(store.get(key, &init), state)),
+            computed: None,
+        },
+    }
+}
 /// Scopes state to some derived subset for all children of this node
 ///
 ///```rust
@@ -219,7 +591,7 @@ pub fn dynamic<'nodes, State>(
 ///     ])
 /// });
 ///```
-pub fn scope<'nodes, State, Scoped: 'nodes>(
+pub fn scope<'nodes, State, Scoped: 'static>(
     scope: impl Fn(ScopeCtx<'_, '_, Scoped>, &mut State) -> ScopeCtxResult + 'nodes,
     node: Node<'nodes, Scoped>,
 ) -> Node<'nodes, State> {
@@ -228,6 +600,7 @@ pub fn scope<'nodes, State, Scoped: 'nodes>(
             node: Box::new(Scoper {
                 scope_fn: scope,
                 node,
+                key: 0,
             }),
         },
     }
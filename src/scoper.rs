@@ -2,7 +2,9 @@ use std::fmt::Debug;
 
 use crate::{
     constraints::SizeConstraints,
-    models::{Area, XAlign, YAlign},
+    context::LayoutCtx,
+    lifecycle::fire_disappeared,
+    models::{Area, PointerEvent, Propagation, XAlign, YAlign},
     traits::NodeTrait,
     Node,
 };
@@ -10,6 +12,9 @@ use crate::{
 pub(crate) struct Scoper<'n, SubState, ScopeStateFn> {
     pub(crate) scope_fn: ScopeStateFn,
     pub(crate) node: Node<'n, SubState>,
+    /// The scope's position in the tree in the most recent pass,
+    /// which identifies it's `Node::on_disappear` callbacks in the store
+    pub(crate) key: u64,
 }
 
 /// Anonymous result to return from the closure passed to `nodes::scope`
@@ -21,6 +26,7 @@ pub struct ScopeCtxResult {
 enum ResultValue {
     Void,
     Constraints(Option<SizeConstraints>),
+    Propagation(Propagation),
 }
 
 impl<SubState, ScopeStateFn> Debug for Scoper<'_, SubState, ScopeStateFn> {
@@ -37,6 +43,9 @@ type WithScopedFnPointer<SubState> = fn(
     contextual_x_align: Option<XAlign>,
     contextual_y_align: Option<YAlign>,
     contextual_visibility: bool,
+    ctx: Option<&mut LayoutCtx>,
+    dispatch: Option<(PointerEvent, &[usize], &[usize])>,
+    key: Option<u64>,
     &mut Node<SubState>,
     &mut SubState,
 ) -> ResultValue;
@@ -48,6 +57,9 @@ pub struct ScopeCtx<'a, 'nodes, SubState> {
     contextual_x_align: Option<XAlign>,
     contextual_y_align: Option<YAlign>,
     contextual_visibility: bool,
+    ctx: Option<&'a mut LayoutCtx>,
+    dispatch: Option<(PointerEvent, &'a [usize], &'a [usize])>,
+    key: Option<u64>,
     with_scoped: WithScopedFnPointer<SubState>,
 }
 
@@ -60,6 +72,9 @@ impl<SubState> ScopeCtx<'_, '_, SubState> {
                 self.contextual_x_align,
                 self.contextual_y_align,
                 self.contextual_visibility,
+                self.ctx,
+                self.dispatch,
+                self.key,
                 self.node,
                 scoped,
             ),
@@ -77,9 +92,15 @@ impl<SubState> ScopeCtx<'_, '_, SubState> {
 impl<'nodes, State, SubState, ScopeStateFn> NodeTrait<State>
     for Scoper<'nodes, SubState, ScopeStateFn>
 where
+    SubState: 'static,
     ScopeStateFn: Fn(ScopeCtx<'_, 'nodes, SubState>, &mut State) -> ScopeCtxResult,
 {
-    fn constraints(&mut self, available_area: Area, state: &mut State) -> Option<SizeConstraints> {
+    fn constraints(
+        &mut self,
+        available_area: Area,
+        state: &mut State,
+        ctx: &mut LayoutCtx,
+    ) -> Option<SizeConstraints> {
         let ScopeCtxResult {
             value: ResultValue::Constraints(constraints),
         } = (self.scope_fn)(
@@ -89,13 +110,23 @@ where
                 contextual_x_align: None,
                 contextual_y_align: None,
                 contextual_visibility: false,
+                ctx: Some(ctx),
+                dispatch: None,
+                key: None,
                 with_scoped: |area: Area,
                               _contextual_x_align: Option<XAlign>,
                               _contextual_y_align: Option<YAlign>,
                               _contextual_visibility: bool,
+                              ctx: Option<&mut LayoutCtx>,
+                              _dispatch: Option<(PointerEvent, &[usize], &[usize])>,
+                              _key: Option<u64>,
                               node: &mut Node<SubState>,
                               sc: &mut SubState| {
-                    ResultValue::Constraints(node.inner.constraints(area, sc))
+                    ResultValue::Constraints(node.inner.constraints(
+                        area,
+                        sc,
+                        ctx.expect("Layout context is provided when computing constraints"),
+                    ))
                 },
             },
             state,
@@ -112,7 +143,9 @@ where
         contextual_x_align: Option<XAlign>,
         contextual_y_align: Option<YAlign>,
         state: &mut State,
+        ctx: &mut LayoutCtx,
     ) {
+        self.key = ctx.position_key();
         let ScopeCtxResult {
             value: ResultValue::Void,
         } = (self.scope_fn)(
@@ -122,14 +155,25 @@ where
                 contextual_x_align,
                 contextual_y_align,
                 contextual_visibility: false,
+                ctx: Some(ctx),
+                dispatch: None,
+                key: None,
                 with_scoped: |available_area: Area,
                               contextual_x_align: Option<XAlign>,
                               contextual_y_align: Option<YAlign>,
                               _contextual_visibility: bool,
+                              ctx: Option<&mut LayoutCtx>,
+                              _dispatch: Option<(PointerEvent, &[usize], &[usize])>,
+                              _key: Option<u64>,
                               node: &mut Node<SubState>,
                               sc: &mut SubState| {
-                    node.inner
-                        .layout(available_area, contextual_x_align, contextual_y_align, sc);
+                    node.inner.layout(
+                        available_area,
+                        contextual_x_align,
+                        contextual_y_align,
+                        sc,
+                        ctx.expect("Layout context is provided during layout"),
+                    );
                     ResultValue::Void
                 },
             },
@@ -150,10 +194,16 @@ where
                 contextual_x_align: None,
                 contextual_y_align: None,
                 contextual_visibility,
+                ctx: None,
+                dispatch: None,
+                key: None,
                 with_scoped: |_available_area: Area,
                               _contextual_x_align: Option<XAlign>,
                               _contextual_y_align: Option<YAlign>,
                               contextual_visibility: bool,
+                              _ctx: Option<&mut LayoutCtx>,
+                              _dispatch: Option<(PointerEvent, &[usize], &[usize])>,
+                              _key: Option<u64>,
                               node: &mut Node<SubState>,
                               sc: &mut SubState| {
                     node.inner.draw(sc, contextual_visibility);
@@ -166,4 +216,82 @@ where
             return;
         };
     }
+    fn dispatch(
+        &mut self,
+        event: PointerEvent,
+        targets: &[usize],
+        receivers: &[usize],
+        state: &mut State,
+    ) -> Propagation {
+        let ScopeCtxResult {
+            value: ResultValue::Propagation(propagation),
+        } = (self.scope_fn)(
+            ScopeCtx {
+                node: &mut self.node,
+                area: Area::zero(),
+                contextual_x_align: None,
+                contextual_y_align: None,
+                contextual_visibility: false,
+                ctx: None,
+                dispatch: Some((event, targets, receivers)),
+                key: None,
+                with_scoped: |_available_area: Area,
+                              _contextual_x_align: Option<XAlign>,
+                              _contextual_y_align: Option<YAlign>,
+                              _contextual_visibility: bool,
+                              _ctx: Option<&mut LayoutCtx>,
+                              dispatch: Option<(PointerEvent, &[usize], &[usize])>,
+                              _key: Option<u64>,
+                              node: &mut Node<SubState>,
+                              sc: &mut SubState| {
+                    let (event, targets, receivers) =
+                        dispatch.expect("Pointer event is provided during dispatch");
+                    ResultValue::Propagation(node.inner.dispatch(event, targets, receivers, sc))
+                },
+            },
+            state,
+        )
+        else {
+            return Propagation::Continue;
+        };
+        propagation
+    }
+    fn fire_lifecycle(&mut self, state: &mut State, ctx: &mut LayoutCtx) {
+        let ScopeCtxResult {
+            value: ResultValue::Void,
+        } = (self.scope_fn)(
+            ScopeCtx {
+                node: &mut self.node,
+                area: Area::zero(),
+                contextual_x_align: None,
+                contextual_y_align: None,
+                contextual_visibility: false,
+                ctx: Some(ctx),
+                dispatch: None,
+                key: Some(self.key),
+                with_scoped: |_available_area: Area,
+                              _contextual_x_align: Option<XAlign>,
+                              _contextual_y_align: Option<YAlign>,
+                              _contextual_visibility: bool,
+                              ctx: Option<&mut LayoutCtx>,
+                              _dispatch: Option<(PointerEvent, &[usize], &[usize])>,
+                              key: Option<u64>,
+                              node: &mut Node<SubState>,
+                              sc: &mut SubState| {
+                    let ctx = ctx.expect("Layout context is provided for lifecycle callbacks");
+                    let key = key.expect("The scope's key is provided for lifecycle callbacks");
+                    let previous = ctx.store.take_scoped_disappear(key);
+                    fire_disappeared(previous, sc, ctx);
+                    let mut on_disappear = Vec::new();
+                    node.inner.fire_lifecycle(sc, ctx, &mut on_disappear);
+                    ctx.store.retain_scoped_disappear(key, on_disappear);
+                    ResultValue::Void
+                },
+            },
+            state,
+        )
+        else {
+            return;
+        };
+    }
 }
@@ -0,0 +1,306 @@
+use crate::{
+    lifecycle::{DisappearFns, LifecycleFn},
+    models::{Area, Curve, Departure, NodeId},
+};
+use std::{
+    any::Any,
+    collections::{HashMap, HashSet},
+    fmt, mem,
+};
+
+/// Local state of nodes built with [`crate::nodes::stateful`], retained between layout passes.
+///
+/// A [`crate::Layout`] owns it's store, so a layout which is kept between frames
+/// retains state automatically. When a new layout is created every frame, move the store between them
+/// using [`crate::Layout::with_store`] & [`crate::Layout::take_store`].
+///
+/// Values whose key isn't found during a pass are dropped at the end of that pass.
+///
+/// The store also retains the ids & `on_disappear` callbacks found in the most recent pass,
+/// see [`crate::Node::on_appear`] & [`crate::Node::on_disappear`],
+/// & the progress of animations, see [`crate::Node::animate`] & [`crate::Node::matched_geometry`].
+pub struct ComponentStore<State> {
+    pub(crate) nodes: NodeStore,
+    /// `Node::on_disappear` callbacks of the nodes found in the most recent pass,
+    /// outside of any `nodes::scope`. Callbacks inside of a scope use scoped state,
+    /// so they're retained by `NodeStore` instead.
+    pub(crate) on_disappear: DisappearFns<State>,
+}
+
+impl<State> Default for ComponentStore<State> {
+    fn default() -> Self {
+        Self {
+            nodes: NodeStore::default(),
+            on_disappear: Vec::new(),
+        }
+    }
+}
+
+impl<State> fmt::Debug for ComponentStore<State> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ComponentStore")
+            .field("nodes", &self.nodes)
+            .field("on_disappear", &self.on_disappear.len())
+            .finish()
+    }
+}
+
+/// The parts of a [`ComponentStore`] which don't depend on the layout's state, used during a pass
+#[derive(Default)]
+pub(crate) struct NodeStore {
+    current: HashMap<NodeId, Box<dyn Any>>,
+    previous: HashMap<NodeId, Box<dyn Any>>,
+    ids: HashSet<NodeId>,
+    previous_ids: HashSet<NodeId>,
+    geometry: HashMap<NodeId, MatchedGeometry>,
+    departures: HashMap<NodeId, Departing>,
+    /// `Node::on_disappear` callbacks found inside of each `nodes::scope` in the most recent pass,
+    /// along with the position of their scope
+    scoped_disappear: HashMap<NodeId, (u64, Box<dyn Any>)>,
+    previous_scoped_disappear: HashMap<NodeId, (u64, Box<dyn Any>)>,
+}
+
+impl fmt::Debug for NodeStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NodeStore")
+            .field("keys", &self.current.keys().chain(self.previous.keys()))
+            .finish()
+    }
+}
+
+impl NodeStore {
+    pub(crate) fn begin_pass(&mut self) {
+        self.previous.extend(mem::take(&mut self.current));
+    }
+    /// Drops values which weren't found during the pass,
+    /// & starts fading out nodes whose geometry keys weren't found.
+    pub(crate) fn end_pass(&mut self, time: Option<f64>) {
+        self.previous.clear();
+        let departures = &mut self.departures;
+        self.geometry.retain(|key, geometry| {
+            if mem::take(&mut geometry.found) {
+                return true;
+            }
+            if let Some(time) = time {
+                departures.insert(
+                    *key,
+                    Departing {
+                        area: geometry.area,
+                        start: time,
+                        from_opacity: geometry.opacity,
+                        opacity: geometry.opacity,
+                        duration: geometry.duration,
+                    },
+                );
+            }
+            false
+        });
+        departures.retain(|_, departure| {
+            let Some(time) = time else {
+                return false;
+            };
+            departure.opacity = departure.from_opacity
+                * (1. - progress_at(departure.start, time, departure.duration));
+            departure.opacity > 0.
+        });
+    }
+    /// Replaces the ids found in the most recent pass, retaining the ids found in the pass before it
+    pub(crate) fn replace_ids(&mut self, ids: HashSet<NodeId>) {
+        self.previous_ids = mem::replace(&mut self.ids, ids);
+        self.previous_scoped_disappear = mem::take(&mut self.scoped_disappear);
+    }
+    /// Takes the `on_disappear` callbacks found inside of the scope at `scope` in the previous pass
+    pub(crate) fn take_scoped_disappear<State: 'static>(
+        &mut self,
+        scope: u64,
+    ) -> DisappearFns<State> {
+        let ids: Vec<NodeId> = self
+            .previous_scoped_disappear
+            .iter()
+            .filter(|(_, (key, _))| *key == scope)
+            .map(|(id, _)| *id)
+            .collect();
+        ids.into_iter()
+            .filter_map(|id| {
+                let (_, handler) = self.previous_scoped_disappear.remove(&id)?;
+                let handler = handler.downcast::<LifecycleFn<'static, State>>().ok()?;
+                Some((id, *handler))
+            })
+            .collect()
+    }
+    /// Retains the `on_disappear` callbacks found inside of the scope at `scope` in the most recent pass
+    pub(crate) fn retain_scoped_disappear<State: 'static>(
+        &mut self,
+        scope: u64,
+        on_disappear: DisappearFns<State>,
+    ) {
+        for (id, handler) in on_disappear {
+            self.scoped_disappear.insert(id, (scope, Box::new(handler)));
+        }
+    }
+    /// Whether `id` was found in the most recent pass
+    pub(crate) fn found(&self, id: NodeId) -> bool {
+        self.ids.contains(&id)
+    }
+    /// Whether `id` was found in the most recent pass, but not the pass before it
+    pub(crate) fn appeared(&self, id: NodeId) -> bool {
+        self.found(id) && !self.previous_ids.contains(&id)
+    }
+    /// The value stored for `key`, which is created using `init` if there isn't one
+    /// or the stored value has a different type.
+    pub(crate) fn get<T: 'static>(&mut self, key: NodeId, init: impl FnOnce() -> T) -> &mut T {
+        let value = self
+            .current
+            .remove(&key)
+            .or_else(|| self.previous.remove(&key))
+            .filter(|value| value.is::<T>())
+            .unwrap_or_else(|| Box::new(init()));
+        self.current
+            .entry(key)
+            .or_insert(value)
+            .downcast_mut()
+            .expect("values are replaced when their type doesn't match")
+    }
+    /// The area to lay out an animated node in at `time`, along with the animation's progress.
+    ///
+    /// The animation restarts from the current area whenever `target` changes.
+    /// Without a time, animations finish immediately.
+    pub(crate) fn animate(
+        &mut self,
+        key: NodeId,
+        target: Area,
+        time: Option<f64>,
+        curve: Curve,
+        duration: f32,
+    ) -> (Area, f32) {
+        self.get(NodeId::new((key, "animation")), || Animation::at(target))
+            .update(target, time, curve, duration)
+    }
+    /// The area to lay out a node with a geometry key in at `time`,
+    /// along with the progress of it's movement & it's opacity.
+    ///
+    /// Nodes whose key wasn't found in the previous pass fade in,
+    /// unless they're still fading out, in which case they fly back from where they were drawn.
+    pub(crate) fn match_geometry(
+        &mut self,
+        key: NodeId,
+        target: Area,
+        time: Option<f64>,
+        curve: Curve,
+        duration: f32,
+    ) -> (Area, f32, f32) {
+        let departure = self.departures.remove(&key);
+        let appear = || MatchedGeometry {
+            animation: Animation::at(
+                departure
+                    .as_ref()
+                    .map_or(target, |departure| departure.area),
+            ),
+            // Fading in resumes from the opacity the node was fading out at
+            appeared: match (time, departure) {
+                (Some(time), Some(departure)) => time - (departure.opacity * duration) as f64,
+                (Some(time), None) => time,
+                (None, _) => f64::NEG_INFINITY,
+            },
+            area: target,
+            opacity: 1.,
+            duration,
+            found: false,
+        };
+        let geometry = self.geometry.entry(key).or_insert_with(appear);
+        let (area, progress) = geometry.animation.update(target, time, curve, duration);
+        geometry.area = area;
+        geometry.opacity = time.map_or(1., |time| progress_at(geometry.appeared, time, duration));
+        geometry.duration = duration;
+        geometry.found = true;
+        (area, progress, geometry.opacity)
+    }
+    /// Nodes with geometry keys which weren't found in a recent pass & are still fading out
+    pub(crate) fn departures(&self) -> Vec<Departure> {
+        self.departures
+            .iter()
+            .map(|(key, departure)| Departure {
+                key: *key,
+                area: departure.area,
+                opacity: departure.opacity,
+            })
+            .collect()
+    }
+}
+
+/// An animation between two areas, see `Node::animate`
+struct Animation {
+    from: Area,
+    to: Area,
+    /// The time the animation started at
+    start: f64,
+}
+
+impl Animation {
+    /// A finished animation which displays `area`
+    fn at(area: Area) -> Self {
+        Self {
+            from: area,
+            to: area,
+            start: f64::NEG_INFINITY,
+        }
+    }
+    /// The area displayed at `time` & the animation's progress, after moving the animation towards `target`
+    fn update(
+        &mut self,
+        target: Area,
+        time: Option<f64>,
+        curve: Curve,
+        duration: f32,
+    ) -> (Area, f32) {
+        let Some(time) = time else {
+            *self = Animation::at(target);
+            return (target, 1.);
+        };
+        if self.to != target {
+            let current = self.from.lerp(
+                self.to,
+                curve.value(progress_at(self.start, time, duration)),
+            );
+            *self = Animation {
+                from: current,
+                to: target,
+                start: time,
+            };
+        }
+        let progress = progress_at(self.start, time, duration);
+        (self.from.lerp(self.to, curve.value(progress)), progress)
+    }
+}
+
+/// A node with a geometry key, see `Node::matched_geometry`
+struct MatchedGeometry {
+    animation: Animation,
+    /// The time the node started fading in at
+    appeared: f64,
+    /// The area & opacity the node was displayed with in the most recent pass
+    area: Area,
+    opacity: f32,
+    duration: f32,
+    /// Whether the node's key was found in the current pass
+    found: bool,
+}
+
+/// A node with a geometry key which is fading out
+struct Departing {
+    area: Area,
+    /// The time the node started fading out at, & it's opacity at that time
+    start: f64,
+    from_opacity: f32,
+    opacity: f32,
+    duration: f32,
+}
+
+/// The fraction of `duration` which has elapsed between `start` & `time`
+fn progress_at(start: f64, time: f64, duration: f32) -> f32 {
+    if duration > 0. {
+        ((time - start) / duration as f64).clamp(0., 1.) as f32
+    } else {
+        1.
+    }
+}
@@ -0,0 +1,191 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+    use crate::traits::TransitionDrawable;
+    use crate::{ComponentStore, Node};
+
+    fn bar<'nodes>(width: f32, curve: Curve) -> Node<'nodes, ()> {
+        space()
+            .width(width)
+            .id("bar")
+            .animate("bar", curve, 1.)
+            .align(Align::Leading)
+    }
+
+    /// Lays out a new tree using the store from the previous frame,
+    /// returning the width of the bar & whether it's still animating
+    fn frame(store: &mut ComponentStore<()>, tree: Node<()>, time: Option<f64>) -> (f32, bool) {
+        let mut layout = Layout::new(tree).with_store(std::mem::take(store));
+        match time {
+            Some(time) => layout.layout_at(Area::new(0., 0., 100., 100.), time, &mut ()),
+            None => layout.layout(Area::new(0., 0., 100., 100.), &mut ()),
+        }
+        *store = layout.take_store();
+        (layout.area_of("bar").unwrap().width, layout.is_animating())
+    }
+
+    #[test]
+    fn test_curves() {
+        let spring = Curve::Spring {
+            stiffness: 2.,
+            damping: 0.5,
+        };
+        for curve in [Curve::Linear, Curve::Ease, spring] {
+            assert_eq!(curve.value(0.), 0.);
+            assert_eq!(curve.value(1.), 1.);
+            assert_eq!(curve.value(2.), 1.);
+        }
+        assert_eq!(Curve::Ease.value(0.5), 0.5);
+        assert!(Curve::Ease.value(0.25) < 0.25);
+        // An underdamped spring overshoots
+        assert!((0..100).any(|step| spring.value(step as f32 / 100.) > 1.));
+    }
+    #[test]
+    fn test_overdamped_spring() {
+        let critical = Curve::Spring {
+            stiffness: 2.,
+            damping: 1.,
+        };
+        let overdamped = Curve::Spring {
+            stiffness: 2.,
+            damping: 2.,
+        };
+        let values: Vec<f32> = (0..=100)
+            .map(|step| overdamped.value(step as f32 / 100.))
+            .collect();
+        // An overdamped spring doesn't overshoot
+        assert!(values.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(values.iter().all(|value| (0. ..=1.).contains(value)));
+        // & settles more slowly than a critically damped spring
+        for step in 1..100 {
+            let progress = step as f32 / 100.;
+            assert!(overdamped.value(progress) < critical.value(progress));
+        }
+    }
+    #[test]
+    fn test_invalid_springs() {
+        for (stiffness, damping) in [(0., 0.5), (0., 1.), (-1., 0.5), (2., -1.), (f32::NAN, 0.5)] {
+            let spring = Curve::Spring { stiffness, damping };
+            for step in 0..=100 {
+                assert!(spring.value(step as f32 / 100.).is_finite());
+            }
+            assert_eq!(spring.value(1.), 1.);
+        }
+        // A spring without stiffness doesn't move until the end
+        let spring = Curve::Spring {
+            stiffness: 0.,
+            damping: 0.5,
+        };
+        assert_eq!(spring.value(0.5), 0.);
+        // Negative damping is treated as undamped, so it doesn't grow
+        let spring = Curve::Spring {
+            stiffness: 2.,
+            damping: -1.,
+        };
+        assert!((0..100).all(|step| spring.value(step as f32 / 100.).abs() <= 2.));
+    }
+    #[test]
+    fn test_animate_interpolates() {
+        let store = &mut ComponentStore::default();
+        assert_eq!(
+            frame(store, bar(20., Curve::Linear), Some(0.)),
+            (20., false)
+        );
+        assert_eq!(
+            frame(store, bar(60., Curve::Linear), Some(10.)),
+            (20., true)
+        );
+        assert_eq!(
+            frame(store, bar(60., Curve::Linear), Some(10.25)),
+            (30., true)
+        );
+        assert_eq!(
+            frame(store, bar(60., Curve::Linear), Some(11.)),
+            (60., false)
+        );
+    }
+    #[test]
+    fn test_animate_retargets() {
+        let store = &mut ComponentStore::default();
+        frame(store, bar(20., Curve::Linear), Some(0.));
+        frame(store, bar(60., Curve::Linear), Some(0.));
+        assert_eq!(frame(store, bar(60., Curve::Linear), Some(0.5)).0, 40.);
+
+        // Changing the target mid-animation starts again from the current area
+        assert_eq!(frame(store, bar(0., Curve::Linear), Some(0.5)).0, 40.);
+        assert_eq!(frame(store, bar(0., Curve::Linear), Some(1.)).0, 20.);
+    }
+    #[test]
+    fn test_animate_without_clock() {
+        let store = &mut ComponentStore::default();
+        frame(store, bar(20., Curve::Ease), Some(0.));
+        assert_eq!(frame(store, bar(60., Curve::Ease), None), (60., false));
+
+        // Animations started later begin from the area of the most recent pass
+        frame(store, bar(80., Curve::Linear), Some(1.));
+        assert_eq!(frame(store, bar(80., Curve::Linear), Some(1.5)).0, 70.);
+    }
+    #[test]
+    fn test_transition_drawable() {
+        struct Recorder;
+        impl TransitionDrawable<Vec<(f32, f32)>> for Recorder {
+            fn draw(
+                &mut self,
+                area: Area,
+                state: &mut Vec<(f32, f32)>,
+                _visible: bool,
+                progress: f32,
+            ) {
+                state.push((area.x, progress));
+            }
+        }
+        let tree = |x| {
+            row(vec![
+                draw_transition(Recorder)
+                    .width(10.)
+                    .animate("moving", Curve::Linear, 2.)
+                    .offset_x(x),
+                draw_transition(Recorder).width(10.),
+            ])
+        };
+        let mut drawn = vec![];
+        let mut layout = Layout::new(tree(0.));
+        layout.draw_at(Area::new(0., 0., 100., 100.), 0., &mut drawn);
+        let mut layout = Layout::new(tree(40.)).with_store(layout.take_store());
+        layout.draw_at(Area::new(0., 0., 100., 100.), 1., &mut drawn);
+        drawn.clear();
+        layout.draw_at(Area::new(0., 0., 100., 100.), 2., &mut drawn);
+        // Drawables outside of any animation always see a progress of 1
+        assert_eq!(drawn, vec![(50., 1.), (60., 0.5)]);
+    }
+    #[test]
+    fn test_draw_ctx() {
+        let tree = |x| {
+            draw_with_ctx(|_, state: &mut Vec<DrawCtx>, ctx| state.push(ctx))
+                .focusable("a")
+                .animate("moving", Curve::Linear, 2.)
+                .offset_x(x)
+        };
+        let mut drawn = vec![];
+        let mut layout = Layout::new(tree(0.));
+        layout.draw_at(Area::new(0., 0., 100., 100.), 0., &mut drawn);
+        let mut layout = Layout::new(tree(40.))
+            .with_store(layout.take_store())
+            .with_focus(Some(NodeId::new("a")));
+        layout.draw_at(Area::new(0., 0., 100., 100.), 1., &mut drawn);
+        drawn.clear();
+        layout.draw_at(Area::new(0., 0., 100., 100.), 2., &mut drawn);
+        // Animated drawables are also told whether they're focused
+        assert_eq!(
+            drawn,
+            vec![DrawCtx {
+                visible: true,
+                focused: true,
+                progress: 0.5,
+                opacity: 1.,
+            }]
+        );
+    }
+}
@@ -0,0 +1,121 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+    use crate::LayoutCache;
+
+    struct A {
+        builds: usize,
+        key: u64,
+    }
+
+    fn frame(cache: LayoutCache, state: &mut A) -> LayoutCache {
+        frame_at(cache, Area::new(0., 0., 100., 100.), state)
+    }
+
+    fn frame_at(cache: LayoutCache, area: Area, state: &mut A) -> LayoutCache {
+        let key = state.key;
+        let mut layout = Layout::new(column(vec![
+            dynamic(|a: &mut A| {
+                a.builds += 1;
+                space().height(10.)
+            })
+            .cache_key(key),
+            space(),
+        ]))
+        .with_cache(cache);
+        layout.draw(area, state);
+        layout.take_cache()
+    }
+
+    #[test]
+    fn test_cache_between_layouts() {
+        let mut state = A { builds: 0, key: 0 };
+        let cache = frame(LayoutCache::default(), &mut state);
+        let uncached_builds = state.builds;
+        // Only built to lay out
+        let cache = frame(cache, &mut state);
+        assert_eq!(state.builds, uncached_builds + 1);
+        state.key = 1;
+        let cache = frame(cache, &mut state);
+        assert_eq!(state.builds, uncached_builds * 2 + 1);
+        frame(cache, &mut state);
+        assert_eq!(state.builds, uncached_builds * 2 + 2);
+    }
+    #[test]
+    fn test_cache_moved_subtree() {
+        let mut state = A { builds: 0, key: 0 };
+        let cache = frame(LayoutCache::default(), &mut state);
+        let uncached_builds = state.builds;
+        // Only the size of the available area is part of the key
+        let cache = frame_at(cache, Area::new(20., 50., 100., 100.), &mut state);
+        assert_eq!(state.builds, uncached_builds + 1);
+        frame_at(cache, Area::new(20., 50., 100., 50.), &mut state);
+        assert_eq!(state.builds, uncached_builds * 2 + 1);
+    }
+    #[test]
+    fn test_cached_constraints_are_reused() {
+        let mut layout = Layout::new(dynamic(|a: &mut A| {
+            column(vec![
+                space()
+                    .height(a.builds as f32)
+                    .cache_key(a.key)
+                    .id("cached"),
+                space(),
+            ])
+        }));
+        let mut state = A { builds: 10, key: 0 };
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        assert_eq!(layout.area_of("cached"), Some(Area::new(0., 0., 100., 10.)));
+        // The key is unchanged, so the stale constraints are used
+        state.builds = 20;
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        assert_eq!(layout.area_of("cached"), Some(Area::new(0., 0., 100., 10.)));
+        state.key = 1;
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        assert_eq!(layout.area_of("cached"), Some(Area::new(0., 0., 100., 20.)));
+    }
+    #[test]
+    fn test_cached_subtree_is_reused() {
+        let mut layout = Layout::new(column(vec![
+            dynamic(|a: &mut A| {
+                a.builds += 1;
+                space().height(10.).id("cached")
+            })
+            .cache_key_with(|a: &A| a.key),
+            space(),
+        ]));
+        let mut state = A { builds: 0, key: 0 };
+        layout.draw(Area::new(0., 0., 100., 100.), &mut state);
+        let builds = state.builds;
+        // Unchanged subtrees of a retained layout aren't rebuilt, but are still laid out
+        layout.draw(Area::new(0., 0., 100., 100.), &mut state);
+        assert_eq!(state.builds, builds);
+        assert_eq!(layout.area_of("cached"), Some(Area::new(0., 0., 100., 10.)));
+        state.key = 1;
+        layout.draw(Area::new(0., 0., 100., 100.), &mut state);
+        assert!(state.builds > builds);
+        let builds = state.builds;
+        layout.draw(Area::new(0., 0., 50., 100.), &mut state);
+        assert!(state.builds > builds);
+    }
+    #[test]
+    fn test_cache_keys_are_scoped() {
+        let frame = |cache: LayoutCache| {
+            let mut layout = Layout::new(column(vec![
+                space().height(10.).cache_key(0).id("a"),
+                row(vec![space().width(20.).cache_key(0).id("b"), space()]),
+                space().height(30.).cache_key(0).id("c"),
+                space(),
+            ]))
+            .with_cache(cache);
+            layout.layout(Area::new(0., 0., 100., 100.), &mut ());
+            assert_eq!(layout.area_of("a"), Some(Area::new(0., 0., 100., 10.)));
+            assert_eq!(layout.area_of("b"), Some(Area::new(0., 10., 20., 30.)));
+            assert_eq!(layout.area_of("c"), Some(Area::new(0., 40., 100., 30.)));
+            layout.take_cache()
+        };
+        frame(frame(LayoutCache::default()));
+    }
+}
@@ -0,0 +1,134 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+    use crate::traits::LayoutAlgorithm;
+
+    /// Places children side by side, each sized to it's minimum width
+    struct Packed;
+
+    impl LayoutAlgorithm for Packed {
+        fn measure(&self, _available_area: Area, children: &[Constraints]) -> Constraints {
+            Constraints {
+                width: Bounds::fixed(children.iter().filter_map(|c| c.width.min).sum()),
+                height: Bounds::fixed(
+                    children
+                        .iter()
+                        .filter_map(|c| c.height.min)
+                        .fold(0., f32::max),
+                ),
+            }
+        }
+        fn place(&self, available_area: Area, children: &[Constraints]) -> Vec<Area> {
+            let mut x = available_area.x;
+            children
+                .iter()
+                .map(|child| {
+                    let width = child.width.min.unwrap_or(0.);
+                    let area = Area::new(x, available_area.y, width, available_area.height);
+                    x += width;
+                    area
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_custom() {
+        Layout::new(column(vec![
+            custom(
+                Packed,
+                vec![
+                    draw(|a, _: &mut ()| {
+                        assert_eq!(a, Area::new(35., 0., 10., 20.));
+                    })
+                    .width(10.)
+                    .height(20.),
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(45., 5., 20., 10.));
+                    })
+                    .width(20.)
+                    .height(10.),
+                ],
+            ),
+            draw(|a, _| {
+                assert_eq!(a, Area::new(0., 20., 100., 80.));
+            }),
+        ]))
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_custom_missing_areas() {
+        struct First;
+        impl LayoutAlgorithm for First {
+            fn measure(&self, _available_area: Area, _children: &[Constraints]) -> Constraints {
+                Constraints::default()
+            }
+            fn place(&self, available_area: Area, _children: &[Constraints]) -> Vec<Area> {
+                vec![available_area]
+            }
+        }
+        Layout::new(custom(
+            First,
+            vec![
+                draw(|a, _: &mut ()| {
+                    assert_eq!(a, Area::new(0., 0., 100., 100.));
+                }),
+                draw(|a, _| {
+                    assert_eq!(a, Area::new(0., 0., 0., 0.));
+                }),
+            ],
+        ))
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_custom_right_to_left() {
+        Layout::new(custom(
+            Packed,
+            vec![
+                draw(|a, _: &mut ()| {
+                    assert_eq!(a, Area::new(55., 50., 10., 0.));
+                })
+                .width(10.),
+                draw(|a, _| {
+                    assert_eq!(a, Area::new(35., 50., 20., 0.));
+                })
+                .width(20.),
+            ],
+        ))
+        .with_direction(Direction::RightToLeft)
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_custom_hidden_children() {
+        /// Splits the container's width evenly between children
+        struct Even;
+        impl LayoutAlgorithm for Even {
+            fn measure(&self, _available_area: Area, _children: &[Constraints]) -> Constraints {
+                Constraints::default()
+            }
+            fn place(&self, available_area: Area, children: &[Constraints]) -> Vec<Area> {
+                let width = available_area.width / children.len() as f32;
+                (0..children.len())
+                    .map(|i| Area {
+                        x: available_area.x + width * i as f32,
+                        width,
+                        ..available_area
+                    })
+                    .collect()
+            }
+        }
+        let mut layout = Layout::new(custom(
+            Even,
+            vec![
+                space().id("a"),
+                draw(|_, _: &mut ()| panic!("hidden")).visible(false),
+                space().id("c"),
+            ],
+        ));
+        layout.layout(Area::new(0., 0., 100., 100.), &mut ());
+        assert_eq!(layout.area_of("a"), Some(Area::new(0., 0., 50., 100.)));
+        assert_eq!(layout.area_of("c"), Some(Area::new(50., 0., 50., 100.)));
+    }
+}
@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+
+    #[test]
+    fn test_row_right_to_left() {
+        Layout::new({
+            row(vec![
+                draw(|a, _: &mut ()| {
+                    assert_eq!(a, Area::new(80., 0., 20., 100.));
+                })
+                .width(20.),
+                draw(|a, _| {
+                    assert_eq!(a, Area::new(0., 0., 80., 100.));
+                }),
+            ])
+        })
+        .with_direction(Direction::RightToLeft)
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_align_and_padding_right_to_left() {
+        Layout::new({
+            column(vec![
+                draw(|a, _: &mut ()| {
+                    assert_eq!(a, Area::new(90., 0., 10., 10.));
+                })
+                .width(10.)
+                .height(10.)
+                .align(Align::Leading),
+                draw(|a, _| {
+                    assert_eq!(a, Area::new(0., 10., 95., 90.));
+                })
+                .pad_leading(5.),
+            ])
+        })
+        .with_direction(Direction::RightToLeft)
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_offset_is_absolute() {
+        Layout::new({
+            row(vec![
+                draw(|a, _: &mut ()| {
+                    assert_eq!(a, Area::new(85., 0., 10., 100.));
+                })
+                .width(10.)
+                .offset_x(-5.),
+                space(),
+            ])
+        })
+        .with_direction(Direction::RightToLeft)
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_subtree_direction() {
+        Layout::new({
+            // The row is placed in the layout's direction, while it's contents are placed left to right
+            row(vec![
+                draw(|a, _: &mut ()| {
+                    assert_eq!(a, Area::new(0., 0., 10., 100.));
+                })
+                .width(10.),
+                draw(|a, _| {
+                    assert_eq!(a, Area::new(10., 0., 40., 100.));
+                }),
+            ])
+            .width(50.)
+            .align(Align::Trailing)
+            .direction(Direction::LeftToRight)
+        })
+        .with_direction(Direction::RightToLeft)
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+        Layout::new({
+            row(vec![
+                space().width(20.),
+                row(vec![
+                    draw(|a, _: &mut ()| {
+                        assert_eq!(a, Area::new(90., 0., 10., 100.));
+                    })
+                    .width(10.),
+                    draw(|a, _| {
+                        assert_eq!(a, Area::new(20., 0., 70., 100.));
+                    }),
+                ])
+                .direction(Direction::RightToLeft),
+            ])
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_root_right_to_left() {
+        Layout::new({
+            draw(|a, _: &mut ()| {
+                assert_eq!(a, Area::new(90., 0., 10., 100.));
+            })
+            .width(10.)
+            .align(Align::Leading)
+        })
+        .with_direction(Direction::RightToLeft)
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+        Layout::new({
+            stack(vec![draw(|a, _: &mut ()| {
+                assert_eq!(a, Area::new(80., 40., 20., 20.));
+            })
+            .width(20.)
+            .height(20.)])
+            .align(Align::Leading)
+        })
+        .with_direction(Direction::RightToLeft)
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+}
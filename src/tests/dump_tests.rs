@@ -0,0 +1,87 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+
+    #[test]
+    fn test_debug_dump() {
+        let mut layout = Layout::new(column(vec![space().height(10.), space()]));
+        assert_eq!(
+            layout.debug_dump(Area::new(0., 0., 100., 100.), &mut ()),
+            "\
+Column { spacing: 0 } constraints: { width: .., height: 10.. } area: { x: 0, y: 0, width: 100, height: 100 }
+  Explicit { height_min: 10, height_max: 10 } constraints: { width: .., height: 10..=10 } area: { x: 0, y: 0, width: 100, height: 10 }
+    Space constraints: { width: .., height: .. } area: { x: 0, y: 0, width: 100, height: 10 }
+  Space constraints: { width: .., height: .. } area: { x: 0, y: 10, width: 100, height: 90 }
+"
+        );
+    }
+    #[test]
+    fn test_debug_dump_dynamic() {
+        let mut layout = Layout::new(dynamic(|_: &mut ()| {
+            row(vec![draw(|_, _: &mut ()| {}).pad_x(10.).id("padded")]).align_contents(Align::Top)
+        }));
+        assert_eq!(
+            layout.debug_dump(Area::new(0., 0., 100., 100.), &mut ()),
+            format!(
+                "\
+Dynamic constraints: {{ width: .., height: .. }} area: {{ x: 0, y: 0, width: 100, height: 100 }}
+  Row {{ spacing: 0, off_axis_align: Top }} constraints: {{ width: .., height: .. }} area: {{ x: 0, y: 0, width: 100, height: 100 }}
+    Id {{ id: {:?} }} constraints: {{ width: .., height: .. }} area: {{ x: 0, y: 0, width: 100, height: 100 }}
+      Padding {{ leading: 10, trailing: 10, top: 0, bottom: 0 }} constraints: {{ width: .., height: .. }} area: {{ x: 0, y: 0, width: 100, height: 100 }}
+        Draw constraints: {{ width: .., height: .. }} area: {{ x: 10, y: 0, width: 80, height: 100 }}
+",
+                NodeId::new("padded")
+            )
+        );
+        assert_eq!(
+            layout.area_of("padded"),
+            Some(Area::new(0., 0., 100., 100.))
+        );
+    }
+    #[test]
+    fn test_debug_dump_updates_layout() {
+        let mut layout = Layout::new(draw(|area, drawn: &mut Area| *drawn = area).id("bar"));
+        let mut drawn = Area::default();
+        layout.layout(Area::new(0., 0., 100., 100.), &mut drawn);
+        layout.debug_dump(Area::new(0., 0., 50., 50.), &mut drawn);
+        // Drawing & queries after a dump agree with the dumped layout
+        layout.draw_only(&mut drawn);
+        assert_eq!(drawn, Area::new(0., 0., 50., 50.));
+        assert_eq!(layout.area_of("bar"), Some(Area::new(0., 0., 50., 50.)));
+        assert_eq!(layout.hit_test(25., 25.), vec![NodeId::new("bar")]);
+        assert!(layout.hit_test(75., 75.).is_empty());
+    }
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_debug_dump_json() {
+        let mut layout = Layout::new(column(vec![
+            row(vec![space().width(10.), space()]),
+            space(),
+        ]));
+        assert_eq!(
+            layout.debug_dump_json(Area::new(0., 0., 100., 100.), &mut ()),
+            concat!(
+                r#"{"kind":"Column","modifiers":{"spacing":"0"},"#,
+                r#""constraints":{"width":{"lower":10,"upper":null},"height":{"lower":null,"upper":null},"aspect":null},"#,
+                r#""area":{"x":0,"y":0,"width":100,"height":100},"children":["#,
+                r#"{"kind":"Row","modifiers":{"spacing":"0"},"#,
+                r#""constraints":{"width":{"lower":10,"upper":null},"height":{"lower":null,"upper":null},"aspect":null},"#,
+                r#""area":{"x":0,"y":0,"width":100,"height":50},"children":["#,
+                r#"{"kind":"Explicit","modifiers":{"width_min":"10","width_max":"10"},"#,
+                r#""constraints":{"width":{"lower":10,"upper":10},"height":{"lower":null,"upper":null},"aspect":null},"#,
+                r#""area":{"x":0,"y":0,"width":10,"height":50},"children":["#,
+                r#"{"kind":"Space","modifiers":{},"#,
+                r#""constraints":{"width":{"lower":null,"upper":null},"height":{"lower":null,"upper":null},"aspect":null},"#,
+                r#""area":{"x":0,"y":0,"width":10,"height":50}}]},"#,
+                r#"{"kind":"Space","modifiers":{},"#,
+                r#""constraints":{"width":{"lower":null,"upper":null},"height":{"lower":null,"upper":null},"aspect":null},"#,
+                r#""area":{"x":10,"y":0,"width":90,"height":50}}]},"#,
+                r#"{"kind":"Space","modifiers":{},"#,
+                r#""constraints":{"width":{"lower":null,"upper":null},"height":{"lower":null,"upper":null},"aspect":null},"#,
+                r#""area":{"x":0,"y":50,"width":100,"height":50}}]}"#,
+            )
+        );
+    }
+}
@@ -47,4 +47,43 @@ mod tests {
         })
         .draw(Area::new(0., 0., 100., 100.), &mut ());
     }
+    #[test]
+    fn test_retained_dynamic_rebuilds() {
+        struct State {
+            height: f32,
+            builds: usize,
+        }
+        let mut layout = Layout::new(column(vec![
+            dynamic(|state: &mut State| {
+                state.builds += 1;
+                space().height(state.height)
+            })
+            .id("a"),
+            space(),
+        ]));
+        let mut state = State {
+            height: 10.,
+            builds: 0,
+        };
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        assert_eq!(layout.area_of("a"), Some(Area::new(0., 0., 100., 10.)));
+        state.height = 50.;
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        assert_eq!(layout.area_of("a"), Some(Area::new(0., 0., 100., 50.)));
+        // The node measured in each pass is the node laid out
+        assert_eq!(state.builds, 2);
+    }
+    #[test]
+    fn test_retained_stateful_rebuilds() {
+        let mut layout = Layout::new(column(vec![
+            stateful("a", || (), |_, height: &mut f32| space().height(*height)).id("a"),
+            space(),
+        ]));
+        let mut height = 10.;
+        layout.layout(Area::new(0., 0., 100., 100.), &mut height);
+        assert_eq!(layout.area_of("a"), Some(Area::new(0., 0., 100., 10.)));
+        height = 50.;
+        layout.layout(Area::new(0., 0., 100., 100.), &mut height);
+        assert_eq!(layout.area_of("a"), Some(Area::new(0., 0., 100., 50.)));
+    }
 }
@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+
+    #[test]
+    fn test_misplaced_root() {
+        let mut layout = Layout::new(empty::<()>());
+        assert_eq!(
+            layout.try_draw(Area::new(0., 0., 100., 100.), &mut ()),
+            Err(LayoutError::MisplacedNode {
+                path: vec!["Empty".to_string()]
+            })
+        );
+        // Drawing without checking for errors skips the invalid node
+        layout.draw(Area::new(0., 0., 100., 100.), &mut ());
+        let mut layout = Layout::new(group::<()>(vec![space(), space()]));
+        assert!(layout
+            .try_draw(Area::new(0., 0., 100., 100.), &mut ())
+            .is_err());
+    }
+    #[test]
+    fn test_misplaced_dynamic() {
+        let mut layout = Layout::new(column(vec![
+            space(),
+            dynamic(|_: &mut ()| empty()).pad(5.),
+            draw(|_, _: &mut ()| panic!("Nothing is drawn when the layout is invalid")),
+        ]));
+        let error = layout
+            .try_draw(Area::new(0., 0., 100., 100.), &mut ())
+            .unwrap_err();
+        assert_eq!(
+            error,
+            LayoutError::MisplacedNode {
+                path: vec![
+                    "Column".to_string(),
+                    "Padding[1]".to_string(),
+                    "Dynamic[0]".to_string(),
+                    "Empty[0]".to_string(),
+                ]
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "Column > Padding[1] > Dynamic[0] > Empty[0] must be placed in a container"
+        );
+    }
+    #[test]
+    fn test_min_greater_than_max() {
+        let mut layout = Layout::new(column(vec![draw(|a, _: &mut ()| {
+            assert_eq!(a, Area::new(40., 0., 20., 100.));
+        })
+        .width_range(20.0..=10.)]));
+        assert_eq!(
+            layout.try_draw(Area::new(0., 0., 100., 100.), &mut ()),
+            Ok(())
+        );
+        assert_eq!(
+            layout.diagnostics(),
+            &[Diagnostic {
+                path: vec!["Column".to_string(), "Explicit[0]".to_string()],
+                kind: DiagnosticKind::MinGreaterThanMax {
+                    axis: Axis::Horizontal,
+                    min: 20.,
+                    max: 10.,
+                },
+            }]
+        );
+    }
+    #[test]
+    fn test_invalid_sizes() {
+        let mut layout = Layout::new(column(vec![
+            draw(|a, _: &mut ()| {
+                assert_eq!(a, Area::new(0., 0., 100., 20.));
+            })
+            .width(f32::NAN)
+            .height(20.),
+            space().dynamic_height(|_, _| f32::NAN),
+            draw(|_, _: &mut ()| {}).measure(|_, _| MeasuredSize {
+                width: f32::NAN,
+                height: 10.,
+            }),
+            grid(vec![], vec![], vec![space(), space()]),
+        ]));
+        assert_eq!(
+            layout.try_draw(Area::new(0., 0., 100., 100.), &mut ()),
+            Ok(())
+        );
+        let path = |index: usize| vec!["Column".to_string(), format!("Explicit[{index}]")];
+        assert_eq!(
+            layout.diagnostics(),
+            &[
+                Diagnostic {
+                    path: path(0),
+                    kind: DiagnosticKind::NotANumber { source: "width" },
+                },
+                Diagnostic {
+                    path: path(1),
+                    kind: DiagnosticKind::NotANumber {
+                        source: "dynamic_height"
+                    },
+                },
+                Diagnostic {
+                    path: path(2),
+                    kind: DiagnosticKind::NotANumber { source: "measure" },
+                },
+            ]
+        );
+    }
+    #[test]
+    fn test_overflow() {
+        let mut layout = Layout::new(row(vec![
+            space().height(150.),
+            space().width(10.).height(50.),
+        ]));
+        layout.layout(Area::new(0., 0., 100., 100.), &mut ());
+        assert_eq!(
+            layout.diagnostics(),
+            &[Diagnostic {
+                path: vec!["Row".to_string()],
+                kind: DiagnosticKind::Overflow {
+                    axis: Axis::Vertical,
+                    size: 150.,
+                    available: 100.,
+                },
+            }]
+        );
+        layout.layout(Area::new(0., 0., 100., 200.), &mut ());
+        assert!(layout.diagnostics().is_empty());
+
+        let mut layout = Layout::new(column(vec![space(), space().width(150.)]).width(100.));
+        layout.layout(Area::new(0., 0., 100., 100.), &mut ());
+        assert_eq!(
+            layout.diagnostics(),
+            &[Diagnostic {
+                path: vec![
+                    "Explicit".to_string(),
+                    "Column[0]".to_string(),
+                    "Explicit[1]".to_string()
+                ],
+                kind: DiagnosticKind::Overflow {
+                    axis: Axis::Horizontal,
+                    size: 150.,
+                    available: 100.,
+                },
+            }]
+        );
+    }
+}
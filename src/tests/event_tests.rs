@@ -0,0 +1,289 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+    use crate::scoper::ScopeCtx;
+    use crate::Node;
+
+    fn down(x: f32, y: f32) -> PointerEvent {
+        PointerEvent::Down {
+            x,
+            y,
+            button: PointerButton::Primary,
+        }
+    }
+    fn up(x: f32, y: f32, button: PointerButton) -> PointerEvent {
+        PointerEvent::Up { x, y, button }
+    }
+    fn record(
+        node: Node<'static, Vec<&'static str>>,
+        name: &'static str,
+        propagation: Propagation,
+    ) -> Node<'static, Vec<&'static str>> {
+        node.on_pointer(move |event, _, state| {
+            if is_hover(event) {
+                return Propagation::Continue;
+            }
+            state.push(name);
+            propagation
+        })
+    }
+    fn is_hover(event: PointerEvent) -> bool {
+        matches!(
+            event,
+            PointerEvent::Enter { .. } | PointerEvent::Leave { .. }
+        )
+    }
+    fn record_hover(
+        node: Node<'static, Vec<String>>,
+        name: &'static str,
+    ) -> Node<'static, Vec<String>> {
+        node.on_hover(move |hovered, state| {
+            state.push(format!(
+                "{name} {}",
+                if hovered { "enter" } else { "leave" }
+            ))
+        })
+    }
+
+    #[test]
+    fn test_dispatch_bubbles_to_ancestors() {
+        let mut layout = Layout::new(record(
+            column(vec![
+                record(space(), "first", Propagation::Continue),
+                record(space(), "second", Propagation::Continue),
+            ]),
+            "column",
+            Propagation::Continue,
+        ));
+        let mut state = vec![];
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        assert_eq!(
+            layout.dispatch(down(50., 75.), &mut state),
+            Propagation::Continue
+        );
+        assert_eq!(state, vec!["second", "column"]);
+    }
+    #[test]
+    fn test_dispatch_stop() {
+        let mut layout = Layout::new(record(
+            stack(vec![record(
+                record(space(), "inner", Propagation::Stop),
+                "middle",
+                Propagation::Continue,
+            )]),
+            "outer",
+            Propagation::Continue,
+        ));
+        let mut state = vec![];
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        assert_eq!(
+            layout.dispatch(down(50., 50.), &mut state),
+            Propagation::Stop
+        );
+        assert_eq!(state, vec!["inner"]);
+    }
+    #[test]
+    fn test_dispatch_topmost_sibling() {
+        // Overlapping siblings don't receive events, only the topmost node & it's ancestors
+        let mut layout = Layout::new(record(
+            stack(vec![
+                record(space(), "back", Propagation::Continue),
+                record(
+                    space().width(20.).height(20.),
+                    "front",
+                    Propagation::Continue,
+                ),
+            ]),
+            "stack",
+            Propagation::Continue,
+        ));
+        let mut state = vec![];
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        layout.dispatch(down(50., 50.), &mut state);
+        assert_eq!(state, vec!["front", "stack"]);
+        state.clear();
+        layout.dispatch(down(10., 10.), &mut state);
+        assert_eq!(state, vec!["back", "stack"]);
+    }
+    #[test]
+    fn test_dispatch_miss() {
+        let mut layout = Layout::new(record(
+            space().width(10.).height(10.),
+            "space",
+            Propagation::Stop,
+        ));
+        let mut state = vec![];
+        assert_eq!(
+            layout.dispatch(down(50., 50.), &mut state),
+            Propagation::Continue
+        );
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        assert_eq!(
+            layout.dispatch(down(10., 10.), &mut state),
+            Propagation::Continue
+        );
+        assert!(state.is_empty());
+        assert_eq!(
+            layout.dispatch(down(50., 50.), &mut state),
+            Propagation::Stop
+        );
+        assert_eq!(state, vec!["space"]);
+    }
+    #[test]
+    fn test_dispatch_invisible() {
+        let mut layout = Layout::new(record(
+            stack(vec![
+                record(space(), "under", Propagation::Continue),
+                record(space(), "hidden", Propagation::Continue).visible(false),
+            ]),
+            "root",
+            Propagation::Continue,
+        ));
+        let mut state = vec![];
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        layout.dispatch(down(50., 50.), &mut state);
+        assert_eq!(state, vec!["under", "root"]);
+    }
+    #[test]
+    fn test_dispatch_clipped() {
+        let mut layout = Layout::new(record(
+            column(vec![
+                scroll(
+                    Axis::Vertical,
+                    0.,
+                    column(vec![
+                        record(space().height(50.), "shown", Propagation::Continue),
+                        record(space().height(50.), "clipped", Propagation::Continue),
+                    ]),
+                )
+                .height(50.),
+                space().height(50.),
+            ]),
+            "root",
+            Propagation::Continue,
+        ));
+        let mut state = vec![];
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        layout.dispatch(down(50., 25.), &mut state);
+        assert_eq!(state, vec!["shown", "root"]);
+        state.clear();
+        layout.dispatch(down(50., 75.), &mut state);
+        assert_eq!(state, vec!["root"]);
+    }
+    #[test]
+    fn test_dispatch_area_and_event() {
+        let mut layout = Layout::new(
+            row(vec![
+                space(),
+                space().on_pointer(|event, area, state: &mut Vec<(PointerEvent, Area)>| {
+                    if !is_hover(event) {
+                        state.push((event, area));
+                    }
+                    Propagation::Stop
+                }),
+            ])
+            .direction(Direction::RightToLeft),
+        );
+        let mut state = vec![];
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        let event = PointerEvent::Scroll {
+            x: 25.,
+            y: 50.,
+            delta_x: 0.,
+            delta_y: -3.,
+        };
+        layout.dispatch(event, &mut state);
+        assert_eq!(state, vec![(event, Area::new(0., 0., 50., 100.))]);
+    }
+    #[test]
+    fn test_on_click() {
+        let mut layout = Layout::new(record(
+            space().on_click(|_, state: &mut Vec<&'static str>| state.push("click")),
+            "outer",
+            Propagation::Continue,
+        ));
+        let mut state = vec![];
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        layout.dispatch(up(50., 50., PointerButton::Primary), &mut state);
+        assert_eq!(state, vec!["click"]);
+        state.clear();
+        layout.dispatch(up(50., 50., PointerButton::Secondary), &mut state);
+        layout.dispatch(down(50., 50.), &mut state);
+        assert_eq!(state, vec!["outer", "outer"]);
+    }
+    #[test]
+    fn test_dispatch_scoped() {
+        struct A {
+            clicks: usize,
+            bubbled: usize,
+        }
+        let mut layout = Layout::new(
+            scope(
+                |ctx: ScopeCtx<usize>, a: &mut A| ctx.with_scoped(&mut a.clicks),
+                space().on_click(|_, clicks| *clicks += 1),
+            )
+            .on_pointer(|event, _, a: &mut A| {
+                if !is_hover(event) {
+                    a.bubbled += 1;
+                }
+                Propagation::Continue
+            }),
+        );
+        let mut state = A {
+            clicks: 0,
+            bubbled: 0,
+        };
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        layout.dispatch(up(50., 50., PointerButton::Primary), &mut state);
+        assert_eq!((state.clicks, state.bubbled), (1, 0));
+        layout.dispatch(down(50., 50.), &mut state);
+        assert_eq!((state.clicks, state.bubbled), (1, 1));
+    }
+    #[test]
+    fn test_hover() {
+        let mut layout = Layout::new(record_hover(
+            row(vec![
+                record_hover(space(), "left"),
+                record_hover(record_hover(space(), "inner"), "right"),
+            ]),
+            "row",
+        ));
+        let mut state = vec![];
+        let moved = |x, y| PointerEvent::Move { x, y };
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        layout.dispatch(moved(25., 50.), &mut state);
+        assert_eq!(state, vec!["row enter", "left enter"]);
+        state.clear();
+        // Moving within the same nodes doesn't deliver hover events
+        layout.dispatch(moved(30., 50.), &mut state);
+        assert!(state.is_empty());
+        layout.dispatch(moved(75., 50.), &mut state);
+        assert_eq!(state, vec!["left leave", "right enter", "inner enter"]);
+        state.clear();
+        // Hover is retained across layout passes
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        layout.dispatch(moved(80., 50.), &mut state);
+        assert!(state.is_empty());
+        layout.dispatch(PointerEvent::Leave { x: 100., y: 50. }, &mut state);
+        assert_eq!(state, vec!["inner leave", "right leave", "row leave"]);
+        state.clear();
+        layout.dispatch(PointerEvent::Enter { x: 25., y: 50. }, &mut state);
+        assert_eq!(state, vec!["row enter", "left enter"]);
+    }
+    #[test]
+    fn test_hover_events_dont_bubble() {
+        let mut layout = Layout::new(record(
+            stack(vec![space().on_hover(|_, state: &mut Vec<&str>| {
+                state.push("inner hover")
+            })]),
+            "outer",
+            Propagation::Continue,
+        ));
+        let mut state = vec![];
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        layout.dispatch(PointerEvent::Move { x: 50., y: 50. }, &mut state);
+        assert_eq!(state, vec!["inner hover", "outer"]);
+    }
+}
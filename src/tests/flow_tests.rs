@@ -0,0 +1,152 @@
+#[cfg(test)]
+mod tests {
+    use crate::constraints::Constraint;
+    use crate::constraints::SizeConstraints;
+    use crate::context::LayoutCtx;
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+
+    #[test]
+    fn test_flow_single_line() {
+        Layout::new({
+            flow(vec![
+                draw(|a, _: &mut ()| {
+                    assert_eq!(a, Area::new(20., 40., 30., 20.));
+                })
+                .width(30.)
+                .height(20.),
+                draw(|a, _: &mut ()| {
+                    assert_eq!(a, Area::new(50., 40., 30., 20.));
+                })
+                .width(30.)
+                .height(20.),
+            ])
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_flow_wraps() {
+        Layout::new({
+            flow_spaced(
+                10.,
+                5.,
+                vec![
+                    draw(|a, _: &mut ()| {
+                        assert_eq!(a, Area::new(0., 0., 40., 20.));
+                    })
+                    .width(40.)
+                    .height(20.),
+                    draw(|a, _: &mut ()| {
+                        assert_eq!(a, Area::new(50., 0., 40., 20.));
+                    })
+                    .width(40.)
+                    .height(20.),
+                    draw(|a, _: &mut ()| {
+                        assert_eq!(a, Area::new(0., 25., 40., 10.));
+                    })
+                    .width(40.)
+                    .height(10.),
+                ],
+            )
+            .align_contents(Align::TopLeading)
+            .expand()
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_flow_line_alignment() {
+        Layout::new({
+            flow(vec![
+                draw(|a, _: &mut ()| {
+                    assert_eq!(a, Area::new(10., 0., 60., 20.));
+                })
+                .width(60.)
+                .height(20.),
+                draw(|a, _: &mut ()| {
+                    assert_eq!(a, Area::new(70., 0., 30., 10.));
+                })
+                .width(30.)
+                .height(10.),
+                draw(|a, _: &mut ()| {
+                    assert_eq!(a, Area::new(80., 20., 20., 20.));
+                })
+                .width(20.)
+                .height(20.),
+            ])
+            .align_contents(Align::TopTrailing)
+            .expand()
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_flow_in_hugging_column() {
+        Layout::new({
+            column(vec![
+                flow(vec![
+                    draw(|a, _: &mut ()| {
+                        assert_eq!(a, Area::new(20., 0., 60., 10.));
+                    })
+                    .width(60.)
+                    .height(10.),
+                    draw(|a, _: &mut ()| {
+                        assert_eq!(a, Area::new(20., 10., 60., 10.));
+                    })
+                    .width(60.)
+                    .height(10.),
+                ]),
+                draw(|a, _: &mut ()| {
+                    assert_eq!(a, Area::new(0., 20., 100., 80.));
+                }),
+            ])
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_flow_constraints() {
+        assert_eq!(
+            flow_spaced::<()>(
+                10.,
+                5.,
+                vec![
+                    space().width(40.).height(20.),
+                    space().width(30.).height(10.)
+                ]
+            )
+            .inner
+            .constraints(
+                Area::new(0., 0., 100., 100.),
+                &mut (),
+                &mut LayoutCtx::default()
+            ),
+            SizeConstraints {
+                width: Constraint::new(Some(40.), Some(80.)),
+                height: Constraint::new(Some(20.), Some(20.)),
+                ..Default::default()
+            }
+            .into()
+        );
+        assert_eq!(
+            flow_spaced::<()>(
+                10.,
+                5.,
+                vec![
+                    space().width(40.).height(20.),
+                    space().width(30.).height(10.)
+                ]
+            )
+            .inner
+            .constraints(
+                Area::new(0., 0., 50., 100.),
+                &mut (),
+                &mut LayoutCtx::default()
+            ),
+            SizeConstraints {
+                width: Constraint::new(Some(40.), Some(80.)),
+                height: Constraint::new(Some(35.), Some(35.)),
+                ..Default::default()
+            }
+            .into()
+        );
+    }
+}
@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+    use crate::traits::Drawable;
+
+    fn id(id: &str) -> Option<NodeId> {
+        Some(NodeId::new(id))
+    }
+    fn tab_index(index: u32) -> FocusOptions {
+        FocusOptions {
+            tab_index: Some(index),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_focus_tree_order() {
+        let mut layout = Layout::new(column(vec![
+            space().focusable("a"),
+            row(vec![space().focusable("b"), space().focusable("c")]),
+            space(),
+        ]));
+        assert_eq!(layout.focus_next(), None);
+        layout.layout(Area::new(0., 0., 100., 100.), &mut ());
+        assert_eq!(layout.focused(), None);
+        assert_eq!(layout.focus_next(), id("a"));
+        assert_eq!(layout.focus_next(), id("b"));
+        assert_eq!(layout.focus_next(), id("c"));
+        assert_eq!(layout.focus_next(), id("a"));
+        assert_eq!(layout.focus_prev(), id("c"));
+        layout.clear_focus();
+        assert_eq!(layout.focus_prev(), id("c"));
+    }
+    #[test]
+    fn test_focus_tab_index() {
+        let mut layout = Layout::new(column(vec![
+            space().focusable("a"),
+            space().focusable_with("b", tab_index(2)),
+            space().focusable("c"),
+            space().focusable_with("d", tab_index(1)),
+        ]));
+        layout.layout(Area::new(0., 0., 100., 100.), &mut ());
+        let order: Vec<_> = (0..4).map(|_| layout.focus_next()).collect();
+        assert_eq!(order, vec![id("d"), id("b"), id("a"), id("c")]);
+    }
+    #[test]
+    fn test_focus_scope() {
+        let mut layout = Layout::new(column(vec![
+            space().focusable("a"),
+            column(vec![
+                space().focusable("b"),
+                space().focusable_with("c", tab_index(1)),
+            ])
+            .focus_scope(),
+            space().focusable("d"),
+        ]));
+        layout.layout(Area::new(0., 0., 100., 100.), &mut ());
+        assert_eq!(layout.focus_next(), id("a"));
+        assert_eq!(layout.focus_prev(), id("d"));
+        assert_eq!(layout.focus_prev(), id("b"));
+
+        // Focus can't leave the scope once inside of it
+        assert_eq!(layout.focus_next(), id("c"));
+        assert_eq!(layout.focus_next(), id("b"));
+        assert_eq!(layout.focus_next(), id("c"));
+        assert_eq!(layout.focus_prev(), id("b"));
+        assert_eq!(layout.focus_prev(), id("c"));
+    }
+    #[test]
+    fn test_focus_hidden() {
+        let mut layout = Layout::new(column(vec![
+            space().focusable("a"),
+            space().focusable("b").visible(false),
+            space().focusable("c"),
+        ]));
+        layout.layout(Area::new(0., 0., 100., 100.), &mut ());
+        layout.focus("a");
+        assert_eq!(layout.focus_next(), id("c"));
+    }
+    #[test]
+    fn test_focus_removed_node() {
+        let mut layout = Layout::new(column(vec![space().focusable("a"), space().focusable("b")]))
+            .with_focus(id("removed"));
+        layout.layout(Area::new(0., 0., 100., 100.), &mut ());
+        assert_eq!(layout.focused(), id("removed"));
+        assert_eq!(layout.focus_next(), id("a"));
+    }
+    #[test]
+    fn test_focused_drawables() {
+        struct Object;
+        impl Drawable<Vec<(&'static str, bool)>> for Object {
+            fn draw(&mut self, _: Area, _: &mut Vec<(&'static str, bool)>, _: bool) {
+                unreachable!()
+            }
+            fn draw_with_ctx(
+                &mut self,
+                _: Area,
+                state: &mut Vec<(&'static str, bool)>,
+                ctx: DrawCtx,
+            ) {
+                state.push(("object", ctx.focused));
+            }
+        }
+        let tree = || {
+            column(vec![
+                stack(vec![
+                    draw_with_ctx(|_, state: &mut Vec<(&'static str, bool)>, ctx| {
+                        state.push(("closure", ctx.focused))
+                    }),
+                    draw_object(Object),
+                ])
+                .focusable("a"),
+                draw_with_ctx(|_, state: &mut Vec<(&'static str, bool)>, ctx| {
+                    state.push(("unfocused", ctx.focused))
+                })
+                .focusable("b"),
+            ])
+        };
+        let mut state = vec![];
+        Layout::new(tree())
+            .with_focus(id("a"))
+            .draw(Area::new(0., 0., 100., 100.), &mut state);
+        state.sort();
+        assert_eq!(
+            state,
+            vec![("closure", true), ("object", true), ("unfocused", false)]
+        );
+
+        // Focus changes are seen by drawables after the next layout pass
+        let mut layout = Layout::new(tree());
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        layout.focus("b");
+        state.clear();
+        layout.draw_only(&mut state);
+        assert!(state.iter().all(|(_, focused)| !focused));
+        state.clear();
+        layout.draw(Area::new(0., 0., 100., 100.), &mut state);
+        state.sort();
+        assert_eq!(
+            state,
+            vec![("closure", false), ("object", false), ("unfocused", true)]
+        );
+    }
+}
@@ -0,0 +1,169 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+
+    #[test]
+    fn test_grid_tracks() {
+        Layout::new({
+            grid(
+                vec![Track::Fixed(20.), Track::Fraction(1.), Track::Fraction(3.)],
+                vec![Track::Fraction(1.), Track::Fraction(1.)],
+                vec![
+                    draw(|a, _: &mut ()| {
+                        assert_eq!(a, Area::new(0., 0., 20., 50.));
+                    }),
+                    draw(|a, _: &mut ()| {
+                        assert_eq!(a, Area::new(20., 0., 20., 50.));
+                    }),
+                    draw(|a, _: &mut ()| {
+                        assert_eq!(a, Area::new(40., 0., 60., 50.));
+                    }),
+                    draw(|a, _: &mut ()| {
+                        assert_eq!(a, Area::new(0., 50., 20., 50.));
+                    }),
+                    draw(|a, _: &mut ()| {
+                        assert_eq!(a, Area::new(20., 50., 20., 50.));
+                    }),
+                    draw(|a, _: &mut ()| {
+                        assert_eq!(a, Area::new(40., 50., 60., 50.));
+                    }),
+                ],
+            )
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_grid_span_and_placement() {
+        Layout::new({
+            grid_spaced(
+                5.,
+                5.,
+                vec![Track::Fraction(1.), Track::Fraction(1.)],
+                vec![Track::Fixed(10.)],
+                vec![
+                    draw(|a, _: &mut ()| {
+                        assert_eq!(a, Area::new(0., 32.5, 100., 10.));
+                    })
+                    .grid_span(2, 1),
+                    draw(|a, _: &mut ()| {
+                        assert_eq!(a, Area::new(52.5, 47.5, 47.5, 20.));
+                    })
+                    .height(20.)
+                    .grid_cell(1, 1),
+                    draw(|a, _: &mut ()| {
+                        assert_eq!(a, Area::new(0., 47.5, 47.5, 20.));
+                    })
+                    .height(20.),
+                ],
+            )
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_grid_content_constraints() {
+        Layout::new({
+            grid(
+                vec![
+                    Track::Fit,
+                    Track::Range {
+                        min: Some(10.),
+                        max: Some(30.),
+                    },
+                    Track::Fraction(1.),
+                ],
+                vec![Track::Fraction(1.)],
+                vec![
+                    draw(|a, _: &mut ()| {
+                        assert_eq!(a, Area::new(0., 0., 15., 100.));
+                    })
+                    .width(15.),
+                    draw(|a, _: &mut ()| {
+                        assert_eq!(a, Area::new(15., 0., 30., 100.));
+                    })
+                    .width_range(20.0..),
+                    draw(|a, _: &mut ()| {
+                        assert_eq!(a, Area::new(45., 0., 55., 100.));
+                    }),
+                ],
+            )
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_grid_in_hugging_column() {
+        Layout::new({
+            column(vec![
+                grid(
+                    vec![Track::Fraction(1.), Track::Fraction(1.)],
+                    vec![Track::Fit],
+                    vec![
+                        draw(|a, _: &mut ()| {
+                            assert_eq!(a, Area::new(0., 0., 50., 20.));
+                        })
+                        .height(20.),
+                        draw(|a, _: &mut ()| {
+                            assert_eq!(a, Area::new(50., 5., 50., 10.));
+                        })
+                        .height(10.),
+                    ],
+                ),
+                draw(|a, _: &mut ()| {
+                    assert_eq!(a, Area::new(0., 20., 100., 80.));
+                }),
+            ])
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_grid_rows_outside_of_grid() {
+        // Empty rows are added between the grid's rows & the cell
+        Layout::new({
+            grid_spaced(
+                0.,
+                5.,
+                vec![Track::Fixed(20.)],
+                vec![Track::Fixed(10.)],
+                vec![
+                    draw(|a, _: &mut ()| {
+                        assert_eq!(a, Area::new(40., 25., 20., 10.));
+                    })
+                    .height(10.),
+                    draw(|a, _: &mut ()| {
+                        assert_eq!(a, Area::new(40., 65., 20., 10.));
+                    })
+                    .height(10.)
+                    .grid_cell(0, 6),
+                ],
+            )
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_grid_without_tracks() {
+        Layout::new({
+            grid(
+                vec![],
+                vec![],
+                vec![
+                    draw(|a, _: &mut ()| {
+                        assert_eq!(a, Area::new(40., 45., 20., 10.));
+                    })
+                    .width(20.)
+                    .height(10.),
+                    draw(|a, _: &mut ()| {
+                        assert_eq!(a, Area::new(40., 50., 20., 10.));
+                    })
+                    .width(20.)
+                    .height(10.)
+                    .grid_cell(5, 1)
+                    .grid_span(3, usize::MAX),
+                ],
+            )
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+        Layout::new(grid::<()>(vec![], vec![], vec![]))
+            .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+}
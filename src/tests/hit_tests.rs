@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+
+    fn ids(ids: &[&str]) -> Vec<NodeId> {
+        ids.iter().map(NodeId::new).collect()
+    }
+
+    #[test]
+    fn test_hit_test_stack() {
+        let mut layout = Layout::new(
+            stack(vec![
+                space().id("back"),
+                space().width(20.).height(20.).id("front"),
+            ])
+            .id("stack"),
+        );
+        layout.layout(Area::new(0., 0., 100., 100.), &mut ());
+        assert_eq!(layout.hit_test(50., 50.), ids(&["front", "back", "stack"]));
+        assert_eq!(layout.hit_test(10., 10.), ids(&["back", "stack"]));
+        assert_eq!(layout.hit_test(150., 50.), ids(&[]));
+    }
+    #[test]
+    fn test_hit_test_reversed_sequence() {
+        // Columns draw their last element first, so earlier elements are on top
+        let mut layout = Layout::new(column(vec![
+            space().height(50.).id("top").offset_y(25.),
+            space().height(50.).id("bottom"),
+        ]));
+        layout.layout(Area::new(0., 0., 100., 100.), &mut ());
+        assert_eq!(layout.hit_test(50., 60.), ids(&["top", "bottom"]));
+        assert_eq!(layout.hit_test(50., 10.), ids(&[]));
+        assert_eq!(layout.hit_test(50., 90.), ids(&["bottom"]));
+    }
+    #[test]
+    fn test_hit_test_attached() {
+        let mut layout = Layout::new(row(vec![
+            space().id("over base").attach_over(space().id("over")),
+            space().id("under base").attach_under(space().id("under")),
+        ]));
+        layout.layout(Area::new(0., 0., 100., 100.), &mut ());
+        assert_eq!(layout.hit_test(25., 50.), ids(&["over", "over base"]));
+        assert_eq!(layout.hit_test(75., 50.), ids(&["under base", "under"]));
+    }
+    #[test]
+    fn test_hit_test_invisible() {
+        let mut layout = Layout::new(stack(vec![
+            space().id("shown"),
+            stack(vec![space().id("hidden child")])
+                .id("hidden")
+                .visible(false),
+        ]));
+        layout.layout(Area::new(0., 0., 100., 100.), &mut ());
+        assert_eq!(layout.hit_test(50., 50.), ids(&["shown"]));
+    }
+    #[test]
+    fn test_hit_test_scroll_clipping() {
+        let mut layout = Layout::new(
+            scroll(
+                Axis::Vertical,
+                50.,
+                column(vec![
+                    space().height(100.).id("first"),
+                    space().height(100.).id("second"),
+                ]),
+            )
+            .height(100.),
+        );
+        layout.layout(Area::new(0., 0., 100., 100.), &mut ());
+        assert_eq!(layout.hit_test(50., 25.), ids(&["first"]));
+        assert_eq!(layout.hit_test(50., 75.), ids(&["second"]));
+        assert_eq!(layout.hit_test(50., 125.), ids(&[]));
+    }
+}
@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+    use crate::scoper::ScopeCtx;
+
+    #[test]
+    fn test_area_of() {
+        let mut layout = Layout::new({
+            column(vec![
+                draw(|_, _: &mut ()| panic!("layout should not draw"))
+                    .height(10.)
+                    .id("header"),
+                row(vec![
+                    draw(|_, _: &mut ()| panic!("layout should not draw"))
+                        .width(20.)
+                        .id(1),
+                    draw(|_, _: &mut ()| panic!("layout should not draw")).id(2),
+                ])
+                .id("body"),
+            ])
+        });
+        layout.layout(Area::new(0., 0., 100., 100.), &mut ());
+        assert_eq!(layout.area_of("header"), Some(Area::new(0., 0., 100., 10.)));
+        assert_eq!(layout.area_of("body"), Some(Area::new(0., 10., 100., 90.)));
+        assert_eq!(layout.area_of(1), Some(Area::new(0., 10., 20., 90.)));
+        assert_eq!(layout.area_of(2), Some(Area::new(20., 10., 80., 90.)));
+        assert_eq!(layout.area_of(3), None);
+    }
+    #[test]
+    fn test_area_of_dynamic() {
+        struct A {
+            show: bool,
+            b: bool,
+        }
+        let mut layout = Layout::new(dynamic(|a: &mut A| {
+            row(vec![
+                if a.show {
+                    space().width(10.).id("shown")
+                } else {
+                    empty()
+                },
+                scope(
+                    |ctx: ScopeCtx<bool>, a: &mut A| ctx.with_scoped(&mut a.b),
+                    space().id("scoped"),
+                ),
+            ])
+        }));
+        let mut state = A {
+            show: true,
+            b: false,
+        };
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        assert_eq!(layout.area_of("shown"), Some(Area::new(0., 0., 10., 100.)));
+        assert_eq!(
+            layout.area_of("scoped"),
+            Some(Area::new(10., 0., 90., 100.))
+        );
+        state.show = false;
+        layout.draw(Area::new(0., 0., 100., 100.), &mut state);
+        assert_eq!(layout.area_of("shown"), None);
+        assert_eq!(
+            layout.area_of("scoped"),
+            Some(Area::new(0., 0., 100., 100.))
+        );
+    }
+    #[test]
+    fn test_id_integer_types() {
+        let mut layout = Layout::new(row(vec![
+            space::<()>().id(1),
+            space().id((-2_i64, "pair")),
+            space().id(u128::MAX),
+        ]));
+        layout.layout(Area::new(0., 0., 90., 90.), &mut ());
+        assert_eq!(layout.area_of(1_usize), layout.area_of(1_u8));
+        assert!(layout.area_of(1_usize).is_some());
+        assert_eq!(
+            layout.area_of((-2_i8, "pair")),
+            Some(Area::new(30., 0., 30., 90.))
+        );
+        assert_eq!(layout.area_of("1"), None);
+        assert_eq!(
+            layout.area_of(u128::MAX),
+            Some(Area::new(60., 0., 30., 90.))
+        );
+        assert_eq!(layout.area_of(-1_i128), None);
+    }
+}
@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+    use crate::Node;
+
+    struct State {
+        cards: Vec<&'static str>,
+        events: Vec<String>,
+    }
+
+    fn card<'nodes>(name: &'static str) -> Node<'nodes, State> {
+        space()
+            .on_appear(name, move |state: &mut State| {
+                state.events.push(format!("appear {name}"))
+            })
+            .on_disappear(name, move |state: &mut State| {
+                state.events.push(format!("disappear {name}"))
+            })
+    }
+
+    fn cards<'nodes>() -> Node<'nodes, State> {
+        dynamic(|state: &mut State| column(state.cards.iter().map(|name| card(name)).collect()))
+    }
+
+    #[test]
+    fn test_lifecycle() {
+        let mut state = State {
+            cards: vec!["a", "b"],
+            events: vec![],
+        };
+        let mut layout = Layout::new(cards());
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        assert_eq!(state.events, vec!["appear a", "appear b"]);
+
+        state.events.clear();
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        assert!(state.events.is_empty());
+
+        state.cards = vec!["b", "c"];
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        assert_eq!(state.events, vec!["disappear a", "appear c"]);
+
+        state.events.clear();
+        state.cards.clear();
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        state.events.sort();
+        assert_eq!(state.events, vec!["disappear b", "disappear c"]);
+    }
+    #[test]
+    fn test_lifecycle_hidden() {
+        let mut state = State {
+            cards: vec![],
+            events: vec![],
+        };
+        let mut layout = Layout::new(dynamic(|state: &mut State| {
+            card("a").visible(state.cards.is_empty())
+        }));
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        state.cards.push("hidden");
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        assert_eq!(state.events, vec!["appear a"]);
+    }
+    #[test]
+    fn test_lifecycle_store() {
+        let mut state = State {
+            cards: vec!["a"],
+            events: vec![],
+        };
+        let mut layout = Layout::new(cards());
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+
+        // Ids are retained by the store
+        let mut layout = Layout::new(cards()).with_store(layout.take_store());
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        assert_eq!(state.events, vec!["appear a"]);
+
+        let mut layout = Layout::new(cards());
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        assert_eq!(state.events, vec!["appear a", "appear a"]);
+    }
+    #[test]
+    fn test_lifecycle_store_disappear() {
+        let mut state = State {
+            cards: vec!["a", "b"],
+            events: vec![],
+        };
+        let mut layout = Layout::new(cards());
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+
+        // Callbacks are retained by the store
+        state.events.clear();
+        state.cards = vec!["b"];
+        let mut layout = Layout::new(cards()).with_store(layout.take_store());
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        assert_eq!(state.events, vec!["disappear a"]);
+    }
+    #[test]
+    fn test_lifecycle_store_scoped() {
+        struct Outer {
+            inner: State,
+        }
+        let scoped = || {
+            scope(
+                |ctx, state: &mut Outer| ctx.with_scoped(&mut state.inner),
+                cards(),
+            )
+        };
+        let mut state = Outer {
+            inner: State {
+                cards: vec!["a", "b"],
+                events: vec![],
+            },
+        };
+        let mut layout = Layout::new(scoped());
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+
+        // Callbacks inside of a scope are retained by the store
+        state.inner.events.clear();
+        state.inner.cards = vec!["b"];
+        let mut layout = Layout::new(scoped()).with_store(layout.take_store());
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        assert_eq!(state.inner.events, vec!["disappear a"]);
+
+        state.inner.events.clear();
+        state.inner.cards.clear();
+        let mut layout = Layout::new(scoped()).with_store(layout.take_store());
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        assert_eq!(state.inner.events, vec!["disappear b"]);
+    }
+    #[test]
+    fn test_lifecycle_scoped() {
+        struct Outer {
+            inner: State,
+        }
+        let mut state = Outer {
+            inner: State {
+                cards: vec!["a"],
+                events: vec![],
+            },
+        };
+        let mut layout = Layout::new(scope(
+            |ctx, state: &mut Outer| ctx.with_scoped(&mut state.inner),
+            cards(),
+        ));
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        assert_eq!(state.inner.events, vec!["appear a"]);
+
+        state.inner.cards = vec!["b"];
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        assert_eq!(
+            state.inner.events,
+            vec!["appear a", "disappear a", "appear b"]
+        );
+    }
+}
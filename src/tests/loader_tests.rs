@@ -0,0 +1,229 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::loader::*;
+    use crate::models::*;
+    use crate::nodes::*;
+    use crate::traits::Drawable;
+
+    type Drawn = Vec<(&'static str, Area)>;
+
+    fn registry(names: &[&'static str]) -> Registry<'static, Drawn> {
+        names.iter().fold(Registry::new(), |registry, &name| {
+            registry.draw(name, move |area, drawn: &mut Drawn| {
+                drawn.push((name, area))
+            })
+        })
+    }
+
+    fn error(description: &str) -> (usize, usize, String) {
+        let error = Description::parse(description).unwrap_err();
+        (error.line, error.column, error.message)
+    }
+
+    #[test]
+    fn test_load_containers() {
+        let description = r#"
+            row_spaced(10) {
+                column {
+                    slot("a").height(20)
+                    slot("b")
+                }.width(30)
+                stack {
+                    slot("c").align(top_trailing).width(10).height(10),
+                    slot("d").pad_x(5)
+                }
+            }.pad(10)
+        "#;
+        let mut drawn = vec![];
+        Layout::new(load(description, &registry(&["a", "b", "c", "d"])).unwrap())
+            .draw(Area::new(0., 0., 100., 100.), &mut drawn);
+        drawn.sort_by_key(|(name, _)| *name);
+        assert_eq!(
+            drawn,
+            vec![
+                ("a", Area::new(10., 10., 30., 20.)),
+                ("b", Area::new(10., 30., 30., 60.)),
+                ("c", Area::new(80., 10., 10., 10.)),
+                ("d", Area::new(55., 10., 30., 80.)),
+            ]
+        );
+    }
+    #[test]
+    fn test_load_grid() {
+        let description = r#"
+            grid([fixed(20), fraction(1)], [fraction(1), fraction(1)]) {
+                slot("a").grid_cell(1, 1)
+                slot("b").grid_cell(0, 0).grid_span(1, 2)
+            }
+        "#;
+        let mut drawn = vec![];
+        Layout::new(load(description, &registry(&["a", "b"])).unwrap())
+            .draw(Area::new(0., 0., 100., 100.), &mut drawn);
+        drawn.sort_by_key(|(name, _)| *name);
+        assert_eq!(
+            drawn,
+            vec![
+                ("a", Area::new(20., 50., 80., 50.)),
+                ("b", Area::new(0., 0., 20., 100.)),
+            ]
+        );
+    }
+    #[test]
+    fn test_load_modifiers() {
+        let description = r#"
+            stack {
+                slot("a")
+                    .width_range(..20.5)
+                    .height(10)
+                    .offset(-5, 2)
+                    .attach_over { slot("b").pad(1) }
+                    .semantics(button, "Save \"all\"")
+                    .focusable("save")
+                    .tab_index(1)
+                    .focus_neighbor(down, "cancel")
+                    .direction(right_to_left)
+                    .visible(true)
+            }
+        "#;
+        let mut layout = Layout::new(load(description, &registry(&["a", "b"])).unwrap());
+        let mut drawn = vec![];
+        layout.draw(Area::new(0., 0., 100., 100.), &mut drawn);
+        drawn.sort_by_key(|(name, _)| *name);
+        assert_eq!(
+            drawn,
+            vec![
+                ("a", Area::new(34.75, 47., 20.5, 10.)),
+                ("b", Area::new(40.75, 46., 18.5, 8.)),
+            ]
+        );
+        assert_eq!(layout.focus_next(), Some(NodeId::new("save")));
+        let tree = layout.accessibility_tree();
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree[0].role, Role::Button);
+        assert_eq!(tree[0].label, "Save \"all\"");
+    }
+    #[test]
+    fn test_load_registry() {
+        struct Object;
+        impl Drawable<Drawn> for Object {
+            fn draw(&mut self, area: Area, drawn: &mut Drawn, _: bool) {
+                drawn.push(("object", area));
+            }
+        }
+        let registry = Registry::new()
+            .draw_object("object", || Object)
+            .node("node", || space().height(10.));
+        let description = Description::parse(r#"column { slot("object") slot("node") }"#).unwrap();
+        for _ in 0..2 {
+            let mut drawn = vec![];
+            Layout::new(description.build(&registry).unwrap())
+                .draw(Area::new(0., 0., 100., 100.), &mut drawn);
+            assert_eq!(drawn, vec![("object", Area::new(0., 0., 100., 90.))]);
+        }
+    }
+    #[test]
+    fn test_load_unknown_slot() {
+        let error = load("column {\n    slot(\"missing\")\n}", &registry(&[])).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 10));
+        assert_eq!(
+            error.to_string(),
+            "2:10: no slot named \"missing\" is registered"
+        );
+    }
+    #[test]
+    fn test_registry_draw_borrows() {
+        let scale = 2.;
+        let registry = Registry::new().draw("a", |area, drawn: &mut Vec<f32>| {
+            drawn.push(area.width * scale)
+        });
+        let mut drawn = vec![];
+        Layout::new(load("slot(\"a\").width(10)", &registry).unwrap())
+            .draw(Area::new(0., 0., 100., 100.), &mut drawn);
+        assert_eq!(drawn, vec![20.]);
+    }
+    #[test]
+    fn test_load_invalid_modifiers() {
+        let error = load("space.focusable(\"a\").pad(1).tab_index(1)", &registry(&[])).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "1:29: `tab_index` must directly follow `focusable`"
+        );
+        let error = load(
+            "space.focusable(\"a\").tab_index(5000000000)",
+            &registry(&[]),
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "1:32: expected a smaller whole number, found `5000000000`"
+        );
+        let error = load("space.semantic_value(\"Ada\")", &registry(&[])).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "1:7: `semantic_value` must directly follow `semantics`"
+        );
+    }
+    #[test]
+    fn test_load_errors() {
+        assert_eq!(
+            error("column {\n  space\n"),
+            (
+                3,
+                1,
+                "expected a node, found the end of the description".to_string()
+            )
+        );
+        assert_eq!(
+            error("row {\n  space.pad(\"ten\")\n}"),
+            (2, 13, "expected a number, found \"ten\"".to_string())
+        );
+        assert_eq!(
+            error("row { spacer }"),
+            (1, 7, "unknown node `spacer`".to_string())
+        );
+        assert_eq!(
+            error("space.paddding(1)"),
+            (1, 7, "unknown modifier `paddding`".to_string())
+        );
+        assert_eq!(
+            error("space.pad(1, 2)"),
+            (1, 7, "`pad` takes 1 argument, found 2".to_string())
+        );
+        assert_eq!(
+            error("space.align(middle)"),
+            (
+                1,
+                13,
+                "unknown alignment `middle`, expected one of `top`, `center_y`, `bottom`, `leading`, \
+                `center_x`, `trailing`, `top_leading`, `top_center`, `top_trailing`, `center_trailing`, \
+                `bottom_trailing`, `bottom_center`, `bottom_leading`, `center_leading`, `center_center`"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            error("scroll(vertical, 0) { space space }"),
+            (
+                1,
+                1,
+                "`scroll` must contain exactly one child, found 2".to_string()
+            )
+        );
+        assert_eq!(
+            error("space space"),
+            (
+                1,
+                7,
+                "expected the end of the description, found `space`".to_string()
+            )
+        );
+        assert_eq!(
+            error("slot(\"a)"),
+            (1, 6, "unterminated string".to_string())
+        );
+        assert_eq!(
+            error("space.pad(#)"),
+            (1, 11, "unexpected character `#`".to_string())
+        );
+    }
+}
@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout;
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+    use crate::Node;
+
+    type Drawn = Vec<(usize, Area)>;
+
+    fn leaf<'nodes>(index: usize) -> Node<'nodes, Drawn> {
+        draw(move |area, drawn: &mut Drawn| drawn.push((index, area)))
+    }
+
+    fn drawn(node: Node<Drawn>) -> Drawn {
+        let mut drawn = vec![];
+        Layout::new(node).draw(Area::new(0., 0., 100., 100.), &mut drawn);
+        drawn.sort_by_key(|(index, _)| *index);
+        drawn
+    }
+
+    #[test]
+    fn test_macro_matches_nodes() {
+        let expanded = layout! {
+            column(spacing: 10) {
+                row {
+                    leaf(0).width(20),
+                    leaf(1)
+                }
+                .pad(5)
+                stack {
+                    leaf(2)
+                    leaf(3).align(Align::Trailing).width(10)
+                }
+                .height(30)
+            }
+        };
+        let written = column_spaced(
+            10.,
+            vec![
+                row(vec![leaf(0).width(20.), leaf(1)]).pad(5.),
+                stack(vec![leaf(2), leaf(3).align(Align::Trailing).width(10.)]).height(30.),
+            ],
+        );
+        assert_eq!(drawn(expanded), drawn(written));
+    }
+    #[test]
+    fn test_macro_control_flow() {
+        let items = [4, 5];
+        let selected = Some(1);
+        let tree = |show: bool| {
+            layout! {
+                column {
+                    if show {
+                        leaf(0)
+                    } else if let Some(index) = selected {
+                        leaf(index)
+                    } else {
+                        leaf(2)
+                    }
+                    for index in items {
+                        leaf(index).height(10)
+                    }
+                }
+            }
+        };
+        assert_eq!(
+            drawn(tree(true)),
+            vec![
+                (0, Area::new(0., 0., 100., 80.)),
+                (4, Area::new(0., 80., 100., 10.)),
+                (5, Area::new(0., 90., 100., 10.)),
+            ]
+        );
+        assert_eq!(drawn(tree(false))[0].0, 1);
+    }
+    #[test]
+    fn test_macro_containers() {
+        let tree = layout! {
+            flow(spacing: 10) {
+                grid(
+                    columns: vec![Track::Fixed(20.), Track::Fraction(1.)],
+                    rows: vec![Track::Fraction(1.)],
+                    column_gap: 10,
+                ) {
+                    leaf(0)
+                    leaf(1)
+                }
+                .width(60)
+                .height(20)
+                scroll(axis: Axis::Vertical, offset: -5) {
+                    leaf(2).height(50)
+                }
+                .width(30)
+                .height(20)
+            }
+        };
+        assert_eq!(
+            drawn(tree),
+            vec![
+                (0, Area::new(0., 40., 20., 20.)),
+                (1, Area::new(30., 40., 30., 20.)),
+                (2, Area::new(70., 45., 30., 50.)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+    use crate::traits::Drawable;
+    use crate::Node;
+
+    /// Records the opacity each node is drawn with
+    struct Faded;
+
+    impl Drawable<Vec<f32>> for Faded {
+        fn draw(&mut self, _area: Area, _state: &mut Vec<f32>, _visible: bool) {}
+        fn draw_with_ctx(&mut self, _area: Area, state: &mut Vec<f32>, ctx: DrawCtx) {
+            state.push(ctx.opacity);
+        }
+    }
+
+    fn photo<'nodes>() -> Node<'nodes, Vec<f32>> {
+        draw_object(Faded)
+            .id("photo")
+            .matched_geometry("photo", Curve::Linear, 1.)
+    }
+
+    fn list<'nodes>() -> Node<'nodes, Vec<f32>> {
+        row(vec![photo().width(20.).height(20.), space()])
+    }
+
+    fn detail<'nodes>() -> Node<'nodes, Vec<f32>> {
+        column(vec![space(), photo().height(50.)])
+    }
+
+    fn area() -> Area {
+        Area::new(0., 0., 100., 100.)
+    }
+
+    #[test]
+    fn test_matched_geometry_flies_between_trees() {
+        let mut drawn = vec![];
+        let mut layout = Layout::new(list());
+        layout.draw_at(area(), 0., &mut drawn);
+        let start = layout.area_of("photo").unwrap();
+        // The photo wasn't found in a previous pass, so it starts fading in
+        assert_eq!(drawn, vec![0.]);
+        drawn.clear();
+
+        layout = Layout::new(detail()).with_store(layout.take_store());
+        layout.draw_at(area(), 1., &mut drawn);
+        assert_eq!(layout.area_of("photo"), Some(start));
+        layout.draw_at(area(), 1.5, &mut drawn);
+        assert_eq!(
+            layout.area_of("photo"),
+            Some(start.lerp(Area::new(0., 50., 100., 50.), 0.5))
+        );
+        assert!(layout.is_animating());
+        layout.draw_at(area(), 2., &mut drawn);
+        assert_eq!(layout.area_of("photo"), Some(Area::new(0., 50., 100., 50.)));
+        assert!(!layout.is_animating());
+
+        // Matched nodes don't fade
+        assert!(drawn.iter().all(|opacity| *opacity == 1.));
+        assert!(layout.departures().is_empty());
+    }
+    #[test]
+    fn test_matched_geometry_fades() {
+        let mut drawn = vec![];
+        let mut layout = Layout::new(space());
+        layout.draw_at(area(), 0., &mut drawn);
+
+        // Unmatched nodes fade in
+        layout = Layout::new(detail()).with_store(layout.take_store());
+        layout.draw_at(area(), 1., &mut drawn);
+        layout.draw_at(area(), 1.5, &mut drawn);
+        layout.draw_at(area(), 2., &mut drawn);
+        assert_eq!(drawn, vec![0., 0.5, 1.]);
+
+        // Nodes which are no longer found fade out from where they were last drawn
+        layout = Layout::new(space()).with_store(layout.take_store());
+        layout.layout_at(area(), 3., &mut drawn);
+        let departure = Departure {
+            key: NodeId::new("photo"),
+            area: Area::new(0., 50., 100., 50.),
+            opacity: 1.,
+        };
+        assert_eq!(layout.departures(), vec![departure]);
+        layout.layout_at(area(), 3.25, &mut drawn);
+        assert_eq!(
+            layout.departures(),
+            vec![Departure {
+                opacity: 0.75,
+                ..departure
+            }]
+        );
+        assert!(layout.is_animating());
+
+        // Returning nodes fade back in from their current opacity
+        layout = Layout::new(detail()).with_store(layout.take_store());
+        drawn.clear();
+        layout.draw_at(area(), 3.25, &mut drawn);
+        layout.draw_at(area(), 3.5, &mut drawn);
+        assert_eq!(drawn, vec![0.75, 1.]);
+        assert!(layout.departures().is_empty());
+
+        layout = Layout::new(space()).with_store(layout.take_store());
+        layout.layout_at(area(), 4., &mut drawn);
+        layout.layout_at(area(), 5., &mut drawn);
+        assert!(layout.departures().is_empty());
+        assert!(!layout.is_animating());
+    }
+    #[test]
+    fn test_matched_geometry_without_clock() {
+        let mut drawn = vec![];
+        let mut layout = Layout::new(list());
+        layout.draw(area(), &mut drawn);
+        layout = Layout::new(detail()).with_store(layout.take_store());
+        layout.draw(area(), &mut drawn);
+        assert_eq!(layout.area_of("photo"), Some(Area::new(0., 50., 100., 50.)));
+        layout = Layout::new(space()).with_store(layout.take_store());
+        layout.draw(area(), &mut drawn);
+        assert!(layout.departures().is_empty());
+        assert!(drawn.iter().all(|opacity| *opacity == 1.));
+    }
+}
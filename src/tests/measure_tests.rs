@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+    use crate::{LayoutCache, Node};
+    use std::{cell::RefCell, rc::Rc};
+
+    /// Five 10x10 words which wrap onto as many lines as needed
+    fn text(check: impl Fn(Area) + 'static) -> Node<'static, ()> {
+        let words = 5.;
+        draw(move |a, _: &mut ()| check(a)).measure(move |proposal, _| {
+            let per_line = proposal
+                .width
+                .map_or(words, |width| (width / 10.).floor().clamp(1., words));
+            MeasuredSize {
+                width: per_line * 10.,
+                height: (words / per_line).ceil() * 10.,
+            }
+        })
+    }
+
+    #[test]
+    fn test_measure_wraps_in_row() {
+        Layout::new(row(vec![
+            text(|a| assert_eq!(a, Area::new(0., 40., 30., 20.))),
+            draw(|a, _| assert_eq!(a, Area::new(30., 0., 70., 100.))).width(70.),
+        ]))
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_measure_nested_in_row() {
+        // Measuring again is needed through padding & a cached subtree
+        let frame = |cache: LayoutCache| {
+            let mut layout = Layout::new(row(vec![
+                text(|a| assert_eq!(a, Area::new(5., 40., 30., 20.)))
+                    .pad(5.)
+                    .cache_key(0),
+                draw(|a, _| assert_eq!(a, Area::new(40., 0., 60., 100.))).width(60.),
+            ]))
+            .with_cache(cache);
+            layout.draw(Area::new(0., 0., 100., 100.), &mut ());
+            layout.take_cache()
+        };
+        frame(frame(LayoutCache::default()));
+    }
+    #[test]
+    fn test_measure_max_content() {
+        Layout::new(column(vec![
+            text(|a| assert_eq!(a, Area::new(25., 0., 50., 10.))),
+            space(),
+        ]))
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_measure_min_content() {
+        Layout::new(row(vec![
+            text(|a| assert_eq!(a, Area::new(0., 25., 10., 50.))),
+            draw(|a, _| assert_eq!(a, Area::new(10., 0., 90., 100.))).width(90.),
+        ]))
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_measure_proposals() {
+        let proposals = Rc::new(RefCell::new(Vec::<ProposedSize>::new()));
+        let recorded = proposals.clone();
+        Layout::new(
+            space()
+                .measure(move |proposal, _: &mut ()| {
+                    recorded.borrow_mut().push(proposal);
+                    MeasuredSize {
+                        width: 20.,
+                        height: 10.,
+                    }
+                })
+                .width_range(..50.),
+        )
+        .layout(Area::new(0., 0., 100., 100.), &mut ());
+        let proposals = proposals.borrow();
+        assert!(proposals.contains(&ProposedSize {
+            width: Some(0.),
+            height: None,
+        }));
+        assert!(proposals.contains(&ProposedSize {
+            width: None,
+            height: None,
+        }));
+        assert!(proposals.contains(&ProposedSize {
+            width: Some(20.),
+            height: Some(100.),
+        }));
+    }
+}
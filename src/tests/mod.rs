@@ -1,6 +1,31 @@
+pub mod animation_tests;
 pub mod attach_tests;
+pub mod cache_tests;
+pub mod custom_tests;
+pub mod direction_tests;
+pub mod dump_tests;
 pub mod dynamic_tests;
+pub mod error_tests;
+pub mod event_tests;
+pub mod flow_tests;
+pub mod focus_tests;
+pub mod grid_tests;
+pub mod hit_tests;
+pub mod id_tests;
 pub mod layout_tests;
+pub mod lifecycle_tests;
+#[cfg(feature = "loader")]
+pub mod loader_tests;
+#[cfg(feature = "macros")]
+pub mod macro_tests;
+pub mod matched_geometry_tests;
+pub mod measure_tests;
+pub mod navigation_tests;
+pub mod phase_tests;
 pub mod public_api_test;
 pub mod scope_tests;
+pub mod scroll_tests;
+pub mod semantics_tests;
 pub mod sequence_tests;
+pub mod stateful_tests;
+pub mod virtual_list_tests;
@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+    use crate::Node;
+
+    fn id(id: &str) -> Option<NodeId> {
+        Some(NodeId::new(id))
+    }
+    fn at(name: &'static str, x: f32, y: f32) -> Node<'static, ()> {
+        space()
+            .width(10.)
+            .height(10.)
+            .focusable(name)
+            .offset(x, y)
+            .align(Align::TopLeading)
+    }
+
+    #[test]
+    fn test_navigate_grid() {
+        let row_of = |names: [&'static str; 3]| {
+            row(names
+                .into_iter()
+                .map(|name| space().focusable(name))
+                .collect())
+        };
+        let mut layout = Layout::new(column(vec![
+            row_of(["a", "b", "c"]),
+            row_of(["d", "e", "f"]),
+            row_of(["g", "h", "i"]),
+        ]));
+        layout.layout(Area::new(0., 0., 90., 90.), &mut ());
+        assert_eq!(layout.navigate(NavigationDirection::Down), id("a"));
+        let moves = [
+            (NavigationDirection::Right, "b"),
+            (NavigationDirection::Down, "e"),
+            (NavigationDirection::Right, "f"),
+            (NavigationDirection::Right, "f"),
+            (NavigationDirection::Down, "i"),
+            (NavigationDirection::Left, "h"),
+            (NavigationDirection::Left, "g"),
+            (NavigationDirection::Up, "d"),
+            (NavigationDirection::Up, "a"),
+            (NavigationDirection::Up, "a"),
+        ];
+        for (direction, expected) in moves {
+            assert_eq!(layout.navigate(direction), id(expected), "{direction:?}");
+        }
+    }
+    #[test]
+    fn test_navigate_prefers_overlap() {
+        let mut layout = Layout::new(stack(vec![
+            at("current", 0., 0.),
+            at("diagonal", 15., 15.),
+            at("far", 60., 5.),
+            at("near", 30., -5.),
+        ]));
+        layout.layout(Area::new(0., 0., 100., 100.), &mut ());
+        layout.focus("current");
+        assert_eq!(layout.navigate(NavigationDirection::Right), id("near"));
+        assert_eq!(layout.navigate(NavigationDirection::Right), id("far"));
+        assert_eq!(layout.navigate(NavigationDirection::Left), id("current"));
+        // Nodes beside the focused node are further away than nodes below it
+        assert_eq!(layout.navigate(NavigationDirection::Down), id("diagonal"));
+        assert_eq!(layout.navigate(NavigationDirection::Up), id("current"));
+    }
+    #[test]
+    fn test_navigate_weights_distance() {
+        let mut layout = Layout::new(stack(vec![
+            at("current", 0., 0.),
+            at("offset", 40., 20.),
+            at("aligned", 15., 30.),
+        ]));
+        layout.layout(Area::new(0., 0., 100., 100.), &mut ());
+        layout.focus("current");
+        // The closer node wins, despite being further along the other axis
+        assert_eq!(layout.navigate(NavigationDirection::Down), id("offset"));
+        assert_eq!(layout.navigate(NavigationDirection::Down), id("aligned"));
+    }
+    #[test]
+    fn test_navigate_neighbors() {
+        let mut layout = Layout::new(row(vec![
+            space().focusable_with(
+                "a",
+                FocusOptions {
+                    neighbors: vec![
+                        (NavigationDirection::Right, NodeId::new("c")),
+                        (NavigationDirection::Left, NodeId::new("missing")),
+                    ],
+                    ..Default::default()
+                },
+            ),
+            space().focusable("b"),
+            space().focusable("c"),
+        ]));
+        layout.layout(Area::new(0., 0., 90., 90.), &mut ());
+        layout.focus("a");
+        assert_eq!(layout.navigate(NavigationDirection::Right), id("c"));
+        assert_eq!(layout.navigate(NavigationDirection::Left), id("b"));
+        layout.focus("a");
+        assert_eq!(layout.navigate(NavigationDirection::Left), id("a"));
+    }
+    #[test]
+    fn test_navigate_scope() {
+        let mut layout = Layout::new(row(vec![
+            space().focusable("outside"),
+            row(vec![space().focusable("a"), space().focusable("b")]).focus_scope(),
+        ]));
+        layout.layout(Area::new(0., 0., 90., 90.), &mut ());
+        layout.focus("outside");
+        assert_eq!(layout.navigate(NavigationDirection::Right), id("a"));
+        assert_eq!(layout.navigate(NavigationDirection::Left), id("a"));
+        assert_eq!(layout.navigate(NavigationDirection::Right), id("b"));
+    }
+}
@@ -0,0 +1,61 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+
+    #[derive(Default)]
+    struct Counts {
+        built: usize,
+        read: usize,
+        drawn: usize,
+    }
+
+    #[test]
+    fn test_draw_only_reuses_layout() {
+        let mut layout = Layout::new(dynamic(|counts: &mut Counts| {
+            counts.built += 1;
+            column(vec![
+                draw(|a, counts: &mut Counts| {
+                    assert_eq!(a, Area::new(0., 0., 100., 50.));
+                    counts.drawn += 1;
+                }),
+                area_reader(|a, counts: &mut Counts| {
+                    counts.read += 1;
+                    draw(move |area, counts: &mut Counts| {
+                        assert_eq!(area, a);
+                        assert_eq!(area, Area::new(0., 50., 100., 50.));
+                        counts.drawn += 1;
+                    })
+                }),
+            ])
+        }));
+        let mut counts = Counts::default();
+        layout.layout(Area::new(0., 0., 100., 100.), &mut counts);
+        let (built, read) = (counts.built, counts.read);
+        assert_eq!(counts.drawn, 0);
+        layout.draw_only(&mut counts);
+        layout.draw_only(&mut counts);
+        assert_eq!(counts.drawn, 4);
+        assert_eq!((counts.built, counts.read), (built, read));
+    }
+    #[test]
+    fn test_relayout_rereads_area() {
+        let mut layout = Layout::new(area_reader(|a, _: &mut Area| {
+            draw(move |area, drawn: &mut Area| {
+                assert_eq!(area, a);
+                *drawn = area;
+            })
+        }));
+        let mut drawn = Area::zero();
+        layout.draw(Area::new(0., 0., 100., 100.), &mut drawn);
+        assert_eq!(drawn, Area::new(0., 0., 100., 100.));
+        layout.draw(Area::new(0., 0., 50., 20.), &mut drawn);
+        assert_eq!(drawn, Area::new(0., 0., 50., 20.));
+    }
+    #[test]
+    fn test_draw_only_before_layout() {
+        let mut layout = Layout::new(draw(|_, _: &mut ()| panic!("nothing has been laid out")));
+        layout.draw_only(&mut ());
+    }
+}
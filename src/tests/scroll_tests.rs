@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+    use crate::traits::Drawable;
+
+    #[derive(Default)]
+    struct State {
+        drawn: Vec<(usize, Area, bool)>,
+        content_size: f32,
+        viewport_size: f32,
+    }
+
+    struct Item(usize);
+
+    impl Drawable<State> for Item {
+        fn draw(&mut self, area: Area, state: &mut State, visible: bool) {
+            state.drawn.push((self.0, area, visible));
+        }
+    }
+
+    #[test]
+    fn test_scroll_offsets_content() {
+        let mut state = State::default();
+        Layout::new({
+            scroll(
+                Axis::Vertical,
+                40.,
+                column((0..5).map(|i| draw_object(Item(i)).height(30.)).collect()),
+            )
+            .on_content_size(|content, viewport, state: &mut State| {
+                state.content_size = content;
+                state.viewport_size = viewport;
+            })
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut state);
+        assert_eq!(state.content_size, 150.);
+        assert_eq!(state.viewport_size, 100.);
+        state.drawn.sort_by_key(|(i, _, _)| *i);
+        assert_eq!(
+            state.drawn,
+            vec![
+                (0, Area::new(0., -40., 100., 30.), false),
+                (1, Area::new(0., -10., 100., 30.), true),
+                (2, Area::new(0., 20., 100., 30.), true),
+                (3, Area::new(0., 50., 100., 30.), true),
+                (4, Area::new(0., 80., 100., 30.), true),
+            ]
+        );
+    }
+    #[test]
+    fn test_scroll_skips_hidden_closures() {
+        Layout::new({
+            scroll(
+                Axis::Horizontal,
+                100.,
+                row(vec![
+                    draw(|_, _: &mut ()| panic!("outside of the viewport")).width(100.),
+                    draw(|a, _: &mut ()| {
+                        assert_eq!(a, Area::new(0., 0., 100., 100.));
+                    })
+                    .width(100.),
+                    draw(|_, _: &mut ()| panic!("outside of the viewport")).width(100.),
+                ]),
+            )
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_scroll_fills_viewport() {
+        Layout::new({
+            column(vec![
+                scroll(
+                    Axis::Vertical,
+                    0.,
+                    draw(|a, _: &mut ()| {
+                        assert_eq!(a, Area::new(0., 0., 100., 50.));
+                    }),
+                ),
+                draw(|a, _: &mut ()| {
+                    assert_eq!(a, Area::new(0., 50., 100., 50.));
+                }),
+            ])
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_scroll_measures_unbounded() {
+        // Each word is 10 wide & 10 tall, wrapping onto new lines when the proposed width is too small
+        let text = || {
+            draw(|_, _: &mut State| {}).measure(|proposal, _| {
+                let per_line = proposal
+                    .width
+                    .map_or(30., |width| (width / 10.).floor().clamp(1., 30.));
+                MeasuredSize {
+                    width: per_line * 10.,
+                    height: (30. / per_line).ceil() * 10.,
+                }
+            })
+        };
+        let mut state = State::default();
+        Layout::new(scroll(Axis::Horizontal, 0., text()).on_content_size(
+            |content, viewport, state: &mut State| {
+                state.content_size = content;
+                state.viewport_size = viewport;
+            },
+        ))
+        .draw(Area::new(0., 0., 100., 100.), &mut state);
+        assert_eq!((state.content_size, state.viewport_size), (300., 100.));
+
+        // Containers measured without a bound still produce finite areas
+        Layout::new(scroll(
+            Axis::Vertical,
+            0.,
+            column(vec![
+                text(),
+                row(vec![space(), text()]),
+                flow(vec![text(), text()]),
+                grid(vec![Track::Fraction(1.)], vec![], vec![text(), space()]),
+                stack(vec![space(), text()]),
+                draw_object(Item(0)),
+            ]),
+        ))
+        .draw(Area::new(0., 0., 100., 100.), &mut state);
+        assert_eq!(state.drawn.len(), 1);
+        assert!(state.drawn.iter().all(|(_, area, _)| area.y.is_finite()
+            && area.height.is_finite()
+            && area.width == 100.));
+    }
+}
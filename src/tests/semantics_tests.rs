@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+
+    fn leaf(role: Role, label: &str, value: Option<&str>, bounds: Area) -> AccessibilityNode {
+        AccessibilityNode {
+            role,
+            label: label.to_string(),
+            value: value.map(str::to_string),
+            bounds,
+            children: vec![],
+        }
+    }
+
+    #[test]
+    fn test_accessibility_tree() {
+        let mut layout = Layout::new(
+            column(vec![
+                space().semantics_with_value(Role::TextInput, "Name", "Ada"),
+                row(vec![
+                    space().semantics(Role::Button, "Cancel"),
+                    space().semantics(Role::Button, "Save"),
+                ])
+                .pad(10.),
+            ])
+            .semantics(Role::Dialog, "Profile"),
+        );
+        assert!(layout.accessibility_tree().is_empty());
+        layout.layout(Area::new(0., 0., 100., 100.), &mut ());
+        assert_eq!(
+            layout.accessibility_tree(),
+            vec![AccessibilityNode {
+                children: vec![
+                    leaf(
+                        Role::TextInput,
+                        "Name",
+                        Some("Ada"),
+                        Area::new(0., 0., 100., 50.)
+                    ),
+                    leaf(Role::Button, "Cancel", None, Area::new(10., 60., 40., 30.)),
+                    leaf(Role::Button, "Save", None, Area::new(50., 60., 40., 30.)),
+                ],
+                ..leaf(Role::Dialog, "Profile", None, Area::new(0., 0., 100., 100.))
+            }]
+        );
+    }
+    #[test]
+    fn test_accessibility_tree_roots_and_hidden() {
+        let mut layout = Layout::new(stack(vec![
+            space().semantics(Role::Text, "First").attach_over(
+                column(vec![space().semantics(Role::Text, "Hidden child")])
+                    .semantics(Role::Group, "Hidden")
+                    .visible(false),
+            ),
+            space()
+                .width(10.)
+                .height(10.)
+                .semantics(Role::Image, "Second"),
+        ]));
+        layout.layout(Area::new(0., 0., 100., 100.), &mut ());
+        assert_eq!(
+            layout.accessibility_tree(),
+            vec![
+                leaf(Role::Text, "First", None, Area::new(0., 0., 100., 100.)),
+                leaf(Role::Image, "Second", None, Area::new(45., 45., 10., 10.)),
+            ]
+        );
+    }
+    #[test]
+    fn test_accessibility_tree_dynamic_rtl() {
+        let mut layout = Layout::new(dynamic(|label: &mut String| {
+            row(vec![
+                space().semantics(Role::Text, label.clone()),
+                space().semantics(Role::Button, "Ok"),
+            ])
+        }))
+        .with_direction(Direction::RightToLeft);
+        let mut label = "Hello".to_string();
+        layout.layout(Area::new(0., 0., 100., 100.), &mut label);
+        assert_eq!(
+            layout.accessibility_tree(),
+            vec![
+                leaf(Role::Text, "Hello", None, Area::new(50., 0., 50., 100.)),
+                leaf(Role::Button, "Ok", None, Area::new(0., 0., 50., 100.)),
+            ]
+        );
+    }
+}
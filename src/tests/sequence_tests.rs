@@ -2,9 +2,11 @@
 mod tests {
     use crate::constraints::Constraint;
     use crate::constraints::SizeConstraints;
+    use crate::context::LayoutCtx;
     use crate::layout::*;
     use crate::models::*;
     use crate::nodes::*;
+    use crate::Node;
     #[test]
     fn test_column_basic() {
         Layout::new({
@@ -389,12 +391,78 @@ mod tests {
         .draw(Area::new(0., 0., 100., 100.), &mut ());
     }
 
+    #[test]
+    fn test_row_grow() {
+        Layout::new({
+            row(vec![
+                draw(|a, _: &mut ()| {
+                    assert_eq!(a, Area::new(0., 0., 25., 100.));
+                }),
+                draw(|a, _: &mut ()| {
+                    assert_eq!(a, Area::new(25., 0., 75., 100.));
+                })
+                .grow(3.),
+            ])
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+        Layout::new({
+            row(vec![
+                draw(|a, _: &mut ()| {
+                    assert_eq!(a, Area::new(0., 0., 10., 100.));
+                })
+                .width_range(..10.),
+                draw(|a, _: &mut ()| {
+                    assert_eq!(a, Area::new(10., 0., 30., 100.));
+                }),
+                draw(|a, _: &mut ()| {
+                    assert_eq!(a, Area::new(40., 0., 60., 100.));
+                })
+                .grow(2.),
+            ])
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+        Layout::new({
+            row(vec![
+                draw(|a, _: &mut ()| {
+                    assert_eq!(a, Area::new(0., 0., 20., 100.));
+                })
+                .width_range(20.0..)
+                .grow(0.),
+                draw(|a, _: &mut ()| {
+                    assert_eq!(a, Area::new(20., 0., 30., 100.));
+                })
+                .width(30.),
+            ])
+            .align_contents(Align::Leading)
+            .expand()
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut ());
+    }
+    #[test]
+    fn test_column_shrink() {
+        Layout::new({
+            column(vec![
+                draw(|a, _: &mut ()| {
+                    assert_eq!(a, Area::new(0., 0., 100., 35.));
+                }),
+                draw(|a, _: &mut ()| {
+                    assert_eq!(a, Area::new(0., 35., 100., 25.));
+                })
+                .shrink(3.),
+                draw(|a, _: &mut ()| {
+                    assert_eq!(a, Area::new(0., 60., 100., 60.));
+                })
+                .height(60.),
+            ])
+        })
+        .draw(Area::new(0., 0., 100., 120.), &mut ());
+    }
     #[test]
     fn test_constraint_combination() {
         assert_eq!(
             row::<()>(vec![space(), space().height(30.)])
                 .inner
-                .constraints(Area::zero(), &mut ()),
+                .constraints(Area::zero(), &mut (), &mut LayoutCtx::default()),
             SizeConstraints {
                 width: Constraint::none(),
                 height: Constraint::new(Some(30.), None),
@@ -405,7 +473,7 @@ mod tests {
         assert_eq!(
             row::<()>(vec![space().height(40.), space().height(30.)])
                 .inner
-                .constraints(Area::zero(), &mut ()),
+                .constraints(Area::zero(), &mut (), &mut LayoutCtx::default()),
             SizeConstraints {
                 width: Constraint::none(),
                 height: Constraint::new(Some(40.), Some(40.)),
@@ -416,7 +484,7 @@ mod tests {
         assert_eq!(
             column::<()>(vec![space(), space().width(10.)])
                 .inner
-                .constraints(Area::zero(), &mut ()),
+                .constraints(Area::zero(), &mut (), &mut LayoutCtx::default()),
             SizeConstraints {
                 width: Constraint::new(Some(10.), None),
                 height: Constraint::none(),
@@ -427,7 +495,7 @@ mod tests {
         assert_eq!(
             column::<()>(vec![space().width(20.), space().width(10.)])
                 .inner
-                .constraints(Area::zero(), &mut ()),
+                .constraints(Area::zero(), &mut (), &mut LayoutCtx::default()),
             SizeConstraints {
                 width: Constraint::new(Some(20.), Some(20.)),
                 height: Constraint::none(),
@@ -438,7 +506,7 @@ mod tests {
         assert_eq!(
             stack::<()>(vec![space(), space().height(10.)])
                 .inner
-                .constraints(Area::zero(), &mut ()),
+                .constraints(Area::zero(), &mut (), &mut LayoutCtx::default()),
             SizeConstraints {
                 width: Constraint::none(),
                 height: Constraint::new(Some(10.), None),
@@ -449,7 +517,7 @@ mod tests {
         assert_eq!(
             stack::<()>(vec![space().height(20.), space().width(10.)])
                 .inner
-                .constraints(Area::zero(), &mut ()),
+                .constraints(Area::zero(), &mut (), &mut LayoutCtx::default()),
             SizeConstraints {
                 width: Constraint::new(Some(10.), None),
                 height: Constraint::new(Some(20.), None),
@@ -460,7 +528,7 @@ mod tests {
         assert_eq!(
             stack::<()>(vec![space().height(20.), space().height(10.)])
                 .inner
-                .constraints(Area::zero(), &mut ()),
+                .constraints(Area::zero(), &mut (), &mut LayoutCtx::default()),
             SizeConstraints {
                 width: Constraint::none(),
                 height: Constraint::new(Some(20.), Some(20.)),
@@ -471,7 +539,7 @@ mod tests {
         assert_eq!(
             stack::<()>(vec![space().width(20.), space().width(10.)])
                 .inner
-                .constraints(Area::zero(), &mut ()),
+                .constraints(Area::zero(), &mut (), &mut LayoutCtx::default()),
             SizeConstraints {
                 width: Constraint::new(Some(20.), Some(20.)),
                 height: Constraint::none(),
@@ -488,7 +556,7 @@ mod tests {
                 .pad(0.)
                 .width_range(..5.)
                 .inner
-                .constraints(Area::zero(), &mut ()),
+                .constraints(Area::zero(), &mut (), &mut LayoutCtx::default()),
             SizeConstraints {
                 width: Constraint::new(Some(5.), Some(5.)),
                 height: Constraint::none(),
@@ -497,4 +565,85 @@ mod tests {
             .into()
         );
     }
+    fn assert_close(a: f32, b: f32) {
+        // Positions accumulate rounding errors across many children
+        assert!((a - b).abs() < 0.01_f32.max(b.abs() * 1e-5), "{a} != {b}");
+    }
+    fn recorded_row(
+        available: f32,
+        children: impl Fn(usize, Node<'static, Vec<Area>>) -> Node<'static, Vec<Area>>,
+        count: usize,
+    ) -> Vec<Area> {
+        let mut areas = vec![Area::zero(); count];
+        Layout::new(row((0..count)
+            .map(|i| children(i, draw(move |a, areas: &mut Vec<Area>| areas[i] = a)))
+            .collect()))
+        .draw(Area::new(0., 0., available, 10.), &mut areas);
+        areas
+    }
+    #[test]
+    fn test_row_many_staggered_upper_bounds() {
+        // Each child can only grow to it's index + 1,
+        // so space has to be redistributed past a different bound for every child
+        let areas = recorded_row(10000., |i, node| node.width_range(..=(i + 1) as f32), 200);
+        // The first 58 children are capped (1 + 2 + ... + 58 = 1711),
+        // the remaining 142 children share the rest
+        let level = (10000. - 1711.) / 142.;
+        for (i, area) in areas.iter().enumerate() {
+            assert_close(area.width, ((i + 1) as f32).min(level));
+        }
+        assert_close(areas[0].x, 0.);
+        assert_close(areas[199].x + areas[199].width, 10000.);
+        for pair in areas.windows(2) {
+            assert_close(pair[0].x + pair[0].width, pair[1].x);
+        }
+    }
+    #[test]
+    fn test_row_many_lower_bounds() {
+        // Every third child is at least 40 wide, the rest shrink to share what remains
+        let areas = recorded_row(
+            20000.,
+            |i, node| {
+                if i % 3 == 0 {
+                    node.width_range(40.0..)
+                } else {
+                    node
+                }
+            },
+            1000,
+        );
+        let bounded = 334. * 40.;
+        let remaining = (20000. - bounded) / 666.;
+        for (i, area) in areas.iter().enumerate() {
+            assert_close(area.width, if i % 3 == 0 { 40. } else { remaining });
+        }
+        assert_close(areas[999].x + areas[999].width, 20000.);
+    }
+    #[test]
+    fn test_row_many_weighted_shrink() {
+        // Half of the children are fixed, the other half shrink to make room for them, shared 1:3
+        let areas = recorded_row(
+            5000.,
+            |i, node| match i % 4 {
+                0 | 1 => node.width(15.),
+                2 => node.shrink(1.),
+                _ => node.shrink(3.),
+            },
+            500,
+        );
+        for (i, area) in areas.iter().enumerate() {
+            assert_close(area.width, [15., 15., 7.5, 2.5][i % 4]);
+        }
+        assert_close(areas[0].x, 0.);
+        assert_close(areas[499].x + areas[499].width, 5000.);
+    }
+    #[test]
+    fn test_row_many_overflow() {
+        // Fixed children which can't fit overflow evenly on both sides
+        let areas = recorded_row(100., |_, node| node.width(2.), 100);
+        for (i, area) in areas.iter().enumerate() {
+            assert_close(area.x, -50. + i as f32 * 2.);
+            assert_close(area.width, 2.);
+        }
+    }
 }
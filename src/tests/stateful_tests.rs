@@ -0,0 +1,127 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+    use std::{cell::Cell, rc::Rc};
+
+    struct Tracked(Rc<Cell<usize>>);
+
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn test_stateful_persists() {
+        let tree = || {
+            column(vec![
+                stateful(
+                    "a",
+                    || 0,
+                    |value: &mut i32, _: &mut Vec<i32>| {
+                        *value = 1;
+                        space()
+                    },
+                ),
+                stateful(
+                    "b",
+                    || 10,
+                    |value: &mut i32, seen: &mut Vec<i32>| {
+                        seen.push(*value);
+                        *value += 1;
+                        space()
+                    },
+                ),
+            ])
+        };
+        let mut seen = vec![];
+        let mut layout = Layout::new(tree());
+        layout.layout(Area::new(0., 0., 100., 100.), &mut seen);
+        layout.layout(Area::new(0., 0., 100., 100.), &mut seen);
+        let mut layout = Layout::new(tree()).with_store(layout.take_store());
+        layout.layout(Area::new(0., 0., 100., 100.), &mut seen);
+        assert_eq!(seen, (10..10 + seen.len() as i32).collect::<Vec<_>>());
+
+        // Taking the store resets every value
+        layout.take_store();
+        seen.clear();
+        layout.layout(Area::new(0., 0., 100., 100.), &mut seen);
+        assert_eq!(seen[0], 10);
+    }
+    #[test]
+    fn test_stateful_dropped() {
+        let drops = Rc::new(Cell::new(0));
+        let inits = Rc::new(Cell::new(0));
+        let mut layout = Layout::new(dynamic({
+            let (drops, inits) = (drops.clone(), inits.clone());
+            move |shown: &mut bool| {
+                let (drops, inits) = (drops.clone(), inits.clone());
+                if *shown {
+                    stateful(
+                        "tracked",
+                        move || {
+                            inits.set(inits.get() + 1);
+                            Tracked(drops.clone())
+                        },
+                        |_, _| space(),
+                    )
+                } else {
+                    space()
+                }
+            }
+        }));
+        let mut shown = true;
+        layout.layout(Area::new(0., 0., 100., 100.), &mut shown);
+        layout.layout(Area::new(0., 0., 100., 100.), &mut shown);
+        assert_eq!((inits.get(), drops.get()), (1, 0));
+        shown = false;
+        layout.layout(Area::new(0., 0., 100., 100.), &mut shown);
+        assert_eq!((inits.get(), drops.get()), (1, 1));
+        shown = true;
+        layout.layout(Area::new(0., 0., 100., 100.), &mut shown);
+        assert_eq!((inits.get(), drops.get()), (2, 1));
+    }
+    #[test]
+    fn test_stateful_keys() {
+        let tree = |keys: Vec<usize>| {
+            row(keys
+                .into_iter()
+                .map(|key| {
+                    stateful(
+                        key,
+                        move || key * 10,
+                        |value: &mut usize, seen: &mut Vec<usize>| {
+                            seen.push(*value);
+                            space()
+                        },
+                    )
+                })
+                .collect())
+        };
+        let mut seen = vec![];
+        let mut layout = Layout::new(tree(vec![1, 2]));
+        layout.layout(Area::new(0., 0., 100., 100.), &mut seen);
+
+        // Values follow their keys when nodes are reordered, rather than their position
+        let mut layout = Layout::new(tree(vec![2, 3, 1])).with_store(layout.take_store());
+        seen.clear();
+        layout.layout(Area::new(0., 0., 100., 100.), &mut seen);
+        assert_eq!(seen[..3], [20, 30, 10]);
+
+        // A value of a different type replaces the stored value
+        let mut layout = Layout::new(stateful(
+            1,
+            || "new",
+            |value: &mut &str, seen: &mut Vec<usize>| {
+                seen.push(value.len());
+                space()
+            },
+        ))
+        .with_store(layout.take_store());
+        seen.clear();
+        layout.layout(Area::new(0., 0., 100., 100.), &mut seen);
+        assert_eq!(seen[0], 3);
+    }
+}
@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod tests {
+    use crate::layout::*;
+    use crate::models::*;
+    use crate::nodes::*;
+
+    #[derive(Default)]
+    struct State {
+        built: Vec<usize>,
+        drawn: Vec<(usize, Area)>,
+    }
+
+    #[test]
+    fn test_virtual_list_builds_visible_rows() {
+        let mut state = State::default();
+        Layout::new({
+            scroll(
+                Axis::Vertical,
+                505.,
+                virtual_list(
+                    10_000,
+                    |_, _| 10.,
+                    |i, state: &mut State| {
+                        state.built.push(i);
+                        draw(move |a, state: &mut State| state.drawn.push((i, a)))
+                    },
+                ),
+            )
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut state);
+        assert_eq!(state.built, (50..61).collect::<Vec<usize>>());
+        state.drawn.sort_by_key(|(i, _)| *i);
+        assert_eq!(state.drawn.len(), 11);
+        assert_eq!(state.drawn[0], (50, Area::new(0., -5., 100., 10.)));
+        assert_eq!(state.drawn[10], (60, Area::new(0., 95., 100., 10.)));
+    }
+    #[test]
+    fn test_virtual_list_spacing_and_alignment() {
+        let mut state = State::default();
+        Layout::new({
+            column(vec![
+                virtual_list_spaced(
+                    5.,
+                    3,
+                    |i, _| 10. * (i + 1) as f32,
+                    |i, _: &mut State| {
+                        draw(move |a, state: &mut State| state.drawn.push((i, a))).width(20.)
+                    },
+                )
+                .align_contents(Align::Leading),
+                space(),
+            ])
+            .align_contents(Align::Top)
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut state);
+        state.drawn.sort_by_key(|(i, _)| *i);
+        assert_eq!(
+            state.drawn,
+            vec![
+                (0, Area::new(0., 0., 20., 10.)),
+                (1, Area::new(0., 15., 20., 20.)),
+                (2, Area::new(0., 40., 20., 30.)),
+            ]
+        );
+    }
+    #[test]
+    fn test_virtual_list_reads_extents_once() {
+        let mut state = State::default();
+        let mut layout = Layout::new(scroll(
+            Axis::Vertical,
+            0.,
+            virtual_list(
+                100,
+                |i, state: &mut State| {
+                    state.built.push(i);
+                    10.
+                },
+                |_, _| space(),
+            ),
+        ));
+        layout.layout(Area::new(0., 0., 100., 100.), &mut state);
+        assert_eq!(state.built, (0..100).collect::<Vec<usize>>());
+    }
+    #[test]
+    fn test_virtual_list_uniform() {
+        let mut state = State::default();
+        Layout::new({
+            scroll(
+                Axis::Vertical,
+                5_000_005.,
+                virtual_list_uniform_spaced(2., 1_000_000, 8., |i, state: &mut State| {
+                    state.built.push(i);
+                    draw(move |a, state: &mut State| state.drawn.push((i, a)))
+                }),
+            )
+        })
+        .draw(Area::new(0., 0., 100., 100.), &mut state);
+        assert_eq!(state.built, (500_000..=500_010).collect::<Vec<usize>>());
+        state.drawn.sort_by_key(|(i, _)| *i);
+        assert_eq!(state.drawn[0], (500_000, Area::new(0., -5., 100., 8.)));
+    }
+}
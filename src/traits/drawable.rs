@@ -1,4 +1,4 @@
-use crate::models::Area;
+use crate::models::{Area, DrawCtx};
 
 /// An object which can be drawn
 ///
@@ -6,4 +6,26 @@ use crate::models::Area;
 pub trait Drawable<State> {
     /// Called with the laid-out position for this node
     fn draw(&mut self, area: Area, state: &mut State, visible: bool);
+    /// Called with the laid-out position for this node & the context it's drawn in,
+    /// such as whether it's focused or the progress of an enclosing animation, see [`DrawCtx`]
+    ///
+    /// Calls [`Drawable::draw`] by default, override this to draw focus indicators or transitions.
+    fn draw_with_ctx(&mut self, area: Area, state: &mut State, ctx: DrawCtx) {
+        self.draw(area, state, ctx.visible)
+    }
+}
+
+/// An object which can be drawn part of the way through an animation
+///
+/// See `nodes::draw_transition`, `Node::animate` & `Node::matched_geometry`
+pub trait TransitionDrawable<State> {
+    /// Called with the laid-out position for this node & the progress of the innermost enclosing animation,
+    /// see [`DrawCtx::progress`]
+    fn draw(&mut self, area: Area, state: &mut State, visible: bool, progress: f32);
+    /// Called with the laid-out position for this node & the context it's drawn in, see [`DrawCtx`]
+    ///
+    /// Calls [`TransitionDrawable::draw`] by default, override this to fade nodes in & out.
+    fn draw_with_ctx(&mut self, area: Area, state: &mut State, ctx: DrawCtx) {
+        self.draw(area, state, ctx.visible, ctx.progress)
+    }
 }
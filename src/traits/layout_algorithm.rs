@@ -0,0 +1,14 @@
+use crate::models::{Area, Constraints};
+
+/// A user-defined container layout
+///
+/// See `nodes::custom`
+pub trait LayoutAlgorithm {
+    /// Returns the constraints of the container, given the `available_area`
+    /// & the constraints of each of it's visible children when measured in the same area.
+    fn measure(&self, available_area: Area, children: &[Constraints]) -> Constraints;
+    /// Returns the area allocated to each child, in the same order as `children`
+    ///
+    /// Children without an area are laid out with zero size.
+    fn place(&self, available_area: Area, children: &[Constraints]) -> Vec<Area>;
+}
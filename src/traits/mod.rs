@@ -1,5 +1,7 @@
 mod drawable;
+mod layout_algorithm;
 mod nodetrait;
 
-pub use drawable::Drawable;
+pub use drawable::{Drawable, TransitionDrawable};
+pub use layout_algorithm::LayoutAlgorithm;
 pub(crate) use nodetrait::NodeTrait;
@@ -1,17 +1,33 @@
 use crate::{
     constraints::SizeConstraints,
-    models::{Area, XAlign, YAlign},
+    context::LayoutCtx,
+    models::{Area, PointerEvent, Propagation, XAlign, YAlign},
 };
 use std::fmt::Debug;
 
 pub(crate) trait NodeTrait<State>: Debug {
-    fn constraints(&mut self, available_area: Area, state: &mut State) -> Option<SizeConstraints>;
+    fn constraints(
+        &mut self,
+        available_area: Area,
+        state: &mut State,
+        ctx: &mut LayoutCtx,
+    ) -> Option<SizeConstraints>;
     fn layout(
         &mut self,
         available_area: Area,
         contextual_x_align: Option<XAlign>,
         contextual_y_align: Option<YAlign>,
         state: &mut State,
+        ctx: &mut LayoutCtx,
     );
     fn draw(&mut self, state: &mut State, contextual_visibility: bool);
+    fn dispatch(
+        &mut self,
+        event: PointerEvent,
+        targets: &[usize],
+        receivers: &[usize],
+        state: &mut State,
+    ) -> Propagation;
+    /// Calls the lifecycle callbacks within the subtree after a layout pass, see `NodeValue::fire_lifecycle`
+    fn fire_lifecycle(&mut self, state: &mut State, ctx: &mut LayoutCtx);
 }
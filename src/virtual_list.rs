@@ -0,0 +1,88 @@
+pub(crate) type ItemExtentFn<'nodes, State> = Box<dyn Fn(usize, &mut State) -> f32 + 'nodes>;
+
+/// The extents of the elements of a virtual list, see `nodes::virtual_list`
+pub(crate) enum ItemExtents<'nodes, State> {
+    /// Every element has the same extent, see `nodes::virtual_list_uniform`
+    Uniform(f32),
+    /// Each element's extent is read from a closure, see `nodes::virtual_list`
+    Each {
+        extent: ItemExtentFn<'nodes, State>,
+        /// The start & extent of each element, along with the pass they were read in
+        items: Option<(u64, Vec<(f32, f32)>)>,
+    },
+}
+
+impl<State> ItemExtents<'_, State> {
+    /// Reads the extent of each element, unless they were already read during this pass
+    pub(crate) fn prepare(&mut self, count: usize, spacing: f32, state: &mut State, pass: u64) {
+        let ItemExtents::Each { extent, items } = self else {
+            return;
+        };
+        if items
+            .as_ref()
+            .is_some_and(|(read, items)| *read == pass && items.len() == count)
+        {
+            return;
+        }
+        let mut start = 0.;
+        let read = (0..count)
+            .map(|i| {
+                let extent = extent(i, state);
+                let item = (start, extent);
+                start += extent + spacing;
+                item
+            })
+            .collect();
+        *items = Some((pass, read));
+    }
+    /// The start of element `i` relative to the start of the list & it's extent
+    ///
+    /// Extents must be read using [`ItemExtents::prepare`] first.
+    pub(crate) fn item(&self, i: usize, spacing: f32) -> (f32, f32) {
+        match self {
+            ItemExtents::Uniform(extent) => (i as f32 * (extent + spacing), *extent),
+            ItemExtents::Each { items, .. } => items
+                .as_ref()
+                .and_then(|(_, items)| items.get(i).copied())
+                .unwrap_or_default(),
+        }
+    }
+    /// The length of the entire list
+    pub(crate) fn length(&self, count: usize, spacing: f32) -> f32 {
+        match count {
+            0 => 0.,
+            count => {
+                let (start, extent) = self.item(count - 1, spacing);
+                start + extent
+            }
+        }
+    }
+    /// The index of the first element which ends after `offset`
+    pub(crate) fn first_after(&self, offset: f32, count: usize, spacing: f32) -> usize {
+        let ends_after = |i: usize| {
+            let (start, extent) = self.item(i, spacing);
+            start + extent > offset
+        };
+        match self {
+            ItemExtents::Uniform(extent) => {
+                let stride = extent + spacing;
+                if stride <= 0. || !stride.is_finite() {
+                    return 0;
+                }
+                // Rounding can put the estimate one element out
+                let mut i = (((offset - extent) / stride).floor().max(-1.) + 1.) as usize;
+                i = i.min(count);
+                while i > 0 && ends_after(i - 1) {
+                    i -= 1;
+                }
+                while i < count && !ends_after(i) {
+                    i += 1;
+                }
+                i
+            }
+            ItemExtents::Each { items, .. } => items.as_ref().map_or(0, |(_, items)| {
+                items.partition_point(|(start, extent)| start + extent <= offset)
+            }),
+        }
+    }
+}